@@ -0,0 +1,53 @@
+use crate::{boundary::boundary_edges, triangulate, Circle, Edge, Point, Triangle};
+
+/// the triangles of the delaunay triangulation with a circumradius of at most alpha, and the edges bounding them.
+/// the bounding edges form a concave hull of the points, which tightens as alpha decreases,
+/// and equals the convex hull for large enough alpha.
+/// empty if points can't be triangulated
+pub fn alpha_complex(points: &[Point], alpha: f32) -> (Vec<Triangle>, Vec<Edge>) {
+    let triangles = match triangulate(points) {
+        Ok(triangles) => triangles,
+        Err(_) => return (Vec::new(), Vec::new()),
+    };
+
+    let alpha_sqr = alpha * alpha;
+    let kept = triangles
+        .into_iter()
+        .filter(|tri| Circle::from_triangle(tri, points).radius_sqr <= alpha_sqr)
+        .collect::<Vec<_>>();
+
+    let edges = boundary_edges(&kept)
+        .into_iter()
+        .map(|(a, b)| Edge::new(a, b))
+        .collect();
+
+    (kept, edges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn grows_with_alpha() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let points = (0..40)
+            .map(|_| Point::new(rng.gen_range(0.0..10.0), rng.gen_range(0.0..10.0)))
+            .collect::<Vec<_>>();
+
+        let mut previous: Vec<Triangle> = Vec::new();
+        for alpha in [0.5, 1.0, 2.0, 4.0, 8.0, 1000.0] {
+            let (triangles, edges) = alpha_complex(&points, alpha);
+
+            assert!(triangles.len() >= previous.len());
+            for tri in previous.iter() {
+                assert!(triangles.iter().any(|t| t.equivalent(tri)));
+            }
+            assert_eq!(edges.is_empty(), triangles.is_empty());
+            previous = triangles;
+        }
+
+        assert_eq!(previous.len(), triangulate(&points).unwrap().len());
+    }
+}
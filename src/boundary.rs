@@ -0,0 +1,102 @@
+use crate::{
+    edge::Edge,
+    types::{HashMap, PointIdx},
+    Triangle,
+};
+
+/// edges used by only one triangle, ie the edges on the boundary of the triangulation, in no particular order
+pub fn boundary_edges(triangles: &[Triangle]) -> Vec<(PointIdx, PointIdx)> {
+    let mut edge_count = HashMap::<Edge, usize>::default();
+    for tri in triangles {
+        for edge in [
+            Edge::new(tri.index0, tri.index1),
+            Edge::new(tri.index1, tri.index2),
+            Edge::new(tri.index2, tri.index0),
+        ] {
+            *edge_count.entry(edge).or_insert(0) += 1;
+        }
+    }
+
+    let mut edges = edge_count
+        .into_iter()
+        .filter(|(_, count)| *count == 1)
+        .map(|(edge, _)| (edge.index_0, edge.index_1))
+        .collect::<Vec<_>>();
+    edges.sort_unstable();
+    edges
+}
+
+/// the boundary of the triangulation as an ordered loop of point indices, starting at the lowest index.
+/// the first vertex is not repeated at the end. for a convex triangulation this is the convex hull.
+/// if the boundary consists of several loops (eg around holes), only the loop through the lowest index is returned
+pub fn boundary_polygon(triangles: &[Triangle]) -> Vec<PointIdx> {
+    let mut adjacent = HashMap::<PointIdx, Vec<PointIdx>>::default();
+    for (a, b) in boundary_edges(triangles) {
+        adjacent.entry(a).or_default().push(b);
+        adjacent.entry(b).or_default().push(a);
+    }
+
+    let start = match adjacent.keys().min() {
+        Some(start) => *start,
+        None => return Vec::new(),
+    };
+
+    let mut polygon = vec![start];
+    let mut prev = start;
+    let mut current = *adjacent[&start].iter().min().unwrap();
+    // length check guards against cycling forever where loops touch at a vertex
+    while current != start && polygon.len() < adjacent.len() {
+        polygon.push(current);
+        let next = adjacent[&current]
+            .iter()
+            .copied()
+            .filter(|idx| *idx != prev)
+            .min();
+        match next {
+            Some(next) => {
+                prev = current;
+                current = next;
+            }
+            None => break,
+        }
+    }
+
+    polygon
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{triangulate, Point};
+
+    #[test]
+    fn boundary_of_four_points() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(0.0, 1.0),
+        ];
+        let triangles = triangulate(&points).unwrap();
+
+        let edges = boundary_edges(&triangles);
+        assert_eq!(edges, vec![(0, 1), (0, 3), (1, 2), (2, 3)]);
+
+        let polygon = boundary_polygon(&triangles);
+        assert_eq!(polygon, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn boundary_skips_interior_points() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.5, 1.0),
+            Point::new(0.5, 0.5),
+        ];
+        let triangles = triangulate(&points).unwrap();
+
+        let polygon = boundary_polygon(&triangles);
+        assert_eq!(polygon, vec![0, 1, 2]);
+    }
+}
@@ -0,0 +1,319 @@
+use std::borrow::Cow;
+
+use crate::{
+    types::{HashMap, PointIdx},
+    FlipCriterion, InsertionOrder, Point, Triangle, Triangulator, TriangulatorError,
+    TriangulatorOptions,
+};
+
+/// configures a triangulation. the defaults give the same result as triangulate
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TriangulatorBuilder {
+    options: TriangulatorOptions,
+    insertion_order: InsertionOrder,
+    normalize_coordinates: bool,
+    dedup_tolerance: f32,
+}
+
+impl TriangulatorBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// see TriangulatorOptions::geometry_epsilon
+    pub fn geometry_epsilon(mut self, epsilon: f32) -> Self {
+        self.options.geometry_epsilon = epsilon;
+        self
+    }
+
+    /// see TriangulatorOptions::flip_criterion
+    pub fn flip_criterion(mut self, criterion: FlipCriterion) -> Self {
+        self.options.flip_criterion = criterion;
+        self
+    }
+
+    /// see TriangulatorOptions::reject_coincident
+    pub fn reject_coincident(mut self, reject: bool) -> Self {
+        self.options.reject_coincident = reject;
+        self
+    }
+
+    /// see TriangulatorOptions::hull_epsilon
+    pub fn hull_epsilon(mut self, epsilon: f32) -> Self {
+        self.options.hull_epsilon = epsilon;
+        self
+    }
+
+    /// see TriangulatorOptions::max_flips_per_triangle
+    pub fn max_flips_per_triangle(mut self, max_flips: usize) -> Self {
+        self.options.max_flips_per_triangle = max_flips;
+        self
+    }
+
+    pub fn insertion_order(mut self, order: InsertionOrder) -> Self {
+        self.insertion_order = order;
+        self
+    }
+
+    /// moves and scales the points to fit in [-1, 1] before triangulating, which keeps f32 precision
+    /// for points far from the origin. only used by triangulate, the triangles index the given points either way
+    pub fn normalize_coordinates(mut self, normalize: bool) -> Self {
+        self.normalize_coordinates = normalize;
+        self
+    }
+
+    /// points closer than tolerance to an earlier point are left out, as if they were that point.
+    /// 0, the default, only merges identical points. only used by triangulate
+    pub fn dedup_tolerance(mut self, tolerance: f32) -> Self {
+        self.dedup_tolerance = tolerance;
+        self
+    }
+
+    /// a triangulator with these options, ready for initial_triangulation
+    pub fn build(&self) -> Triangulator {
+        let mut triangulator = Triangulator::new();
+        triangulator.set_options(self.options);
+        triangulator.set_insertion_order(self.insertion_order);
+        triangulator
+    }
+
+    /// triangulates points with these options, the triangles index points
+    pub fn triangulate(&self, points: &[Point]) -> Result<Vec<Triangle>, TriangulatorError> {
+        let (unique, index_map) = if self.dedup_tolerance > 0.0 {
+            let (unique, index_map) = dedup_within(points, self.dedup_tolerance);
+            (Cow::Owned(unique), Some(index_map))
+        } else {
+            (Cow::Borrowed(points), None)
+        };
+        let unique = if self.normalize_coordinates {
+            Cow::Owned(normalized(&unique))
+        } else {
+            unique
+        };
+        let to_input = |idx: usize| index_map.as_ref().map_or(idx, |map| map[idx] as usize);
+
+        let mut triangulator = self.build();
+        triangulator
+            .initial_triangulation(&unique)
+            .map(|_| ())
+            .and_then(|_| triangulator.triangulator.add_all(&unique))
+            .map_err(|err| match err {
+                TriangulatorError::NANInInput(i) => TriangulatorError::NANInInput(to_input(i)),
+                err => err,
+            })?;
+
+        let mut triangles = triangulator.into_triangles();
+        if index_map.is_some() {
+            for tri in triangles.iter_mut() {
+                tri.index0 = to_input(tri.index0 as usize) as PointIdx;
+                tri.index1 = to_input(tri.index1 as usize) as PointIdx;
+                tri.index2 = to_input(tri.index2 as usize) as PointIdx;
+            }
+        }
+        Ok(triangles)
+    }
+}
+
+// keeps the first of points within tolerance of each other, with the input index of each kept point
+fn dedup_within(points: &[Point], tolerance: f32) -> (Vec<Point>, Vec<PointIdx>) {
+    let cell = |p: &Point| {
+        (
+            (p.x / tolerance).floor() as i64,
+            (p.y / tolerance).floor() as i64,
+        )
+    };
+    let mut grid = HashMap::<(i64, i64), Vec<usize>>::default();
+    let mut unique = Vec::<Point>::with_capacity(points.len());
+    let mut index_map = Vec::with_capacity(points.len());
+
+    for (i, p) in points.iter().enumerate() {
+        let (cx, cy) = cell(p);
+        let near = (cx - 1..=cx + 1)
+            .flat_map(|x| (cy - 1..=cy + 1).map(move |y| (x, y)))
+            .filter_map(|key| grid.get(&key))
+            .flatten()
+            .any(|&j: &usize| {
+                let d = *p - unique[j];
+                d.x.hypot(d.y) <= tolerance
+            });
+        if !near {
+            grid.entry((cx, cy)).or_default().push(unique.len());
+            unique.push(*p);
+            index_map.push(i as PointIdx);
+        }
+    }
+
+    (unique, index_map)
+}
+
+// the points moved and scaled to fit in [-1, 1], keeping the aspect ratio
+fn normalized(points: &[Point]) -> Vec<Point> {
+    let (mut min_x, mut min_y) = (f32::MAX, f32::MAX);
+    let (mut max_x, mut max_y) = (f32::MIN, f32::MIN);
+    for p in points.iter().filter(|p| p.x.is_finite() && p.y.is_finite()) {
+        min_x = min_x.min(p.x);
+        min_y = min_y.min(p.y);
+        max_x = max_x.max(p.x);
+        max_y = max_y.max(p.y);
+    }
+    let half_extent = 0.5 * (max_x - min_x).max(max_y - min_y);
+    if !(half_extent > 0.0 && half_extent.is_finite()) {
+        return points.to_vec();
+    }
+
+    let center = Point::new(0.5 * (min_x + max_x), 0.5 * (min_y + max_y));
+    points
+        .iter()
+        .map(|p| (*p - center) * (1.0 / half_extent))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn random_points(seed: u64, n: usize) -> Vec<Point> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..n)
+            .map(|_| Point::new(rng.gen_range(0.0..10.0), rng.gen_range(0.0..10.0)))
+            .collect()
+    }
+
+    #[test]
+    fn default_same_as_triangulate() {
+        let mut points = random_points(3, 100);
+        points.push(points[7]);
+
+        assert_eq!(
+            TriangulatorBuilder::new().triangulate(&points).unwrap(),
+            crate::triangulate(&points).unwrap()
+        );
+    }
+
+    #[test]
+    fn flip_limit_reported() {
+        let points = random_points(7, 50);
+        assert_eq!(
+            TriangulatorBuilder::new()
+                .max_flips_per_triangle(0)
+                .triangulate(&points),
+            Err(TriangulatorError::FlipLimitExceeded)
+        );
+
+        // stepwise, after the step that needed a flip
+        let mut triangulator = TriangulatorBuilder::new()
+            .flip_criterion(FlipCriterion::MinMaxAngle)
+            .build();
+        triangulator.initial_triangulation(&points).unwrap();
+        assert!(!triangulator.flip_limit_exceeded());
+        triangulator.set_options(TriangulatorOptions {
+            max_flips_per_triangle: 0,
+            ..triangulator.options()
+        });
+        while triangulator.do_step(&points) {}
+        assert!(triangulator.flip_limit_exceeded());
+    }
+
+    #[test]
+    fn coincident_points_rejected_in_strict_mode() {
+        let mut points = random_points(4, 30);
+        points.push(points[12]);
+        let hull_duplicate = points
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.x.total_cmp(&b.x))
+            .map(|(i, p)| (i, *p))
+            .unwrap();
+        let strict = TriangulatorBuilder::new().reject_coincident(true);
+
+        assert_eq!(
+            strict.triangulate(&points),
+            Err(TriangulatorError::CoincidentPoints(12, 30))
+        );
+        assert!(TriangulatorBuilder::new().triangulate(&points).is_ok());
+
+        points.pop();
+        assert!(strict.triangulate(&points).is_ok());
+        points.push(hull_duplicate.1);
+        assert_eq!(
+            strict.triangulate(&points),
+            Err(TriangulatorError::CoincidentPoints(hull_duplicate.0, 30))
+        );
+    }
+
+    #[test]
+    fn nearly_coincident_points_rejected_within_epsilon() {
+        let mut points = random_points(4, 30);
+        points.push(Point::new(points[12].x * (1.0 + 2e-7), points[12].y));
+        let strict = TriangulatorBuilder::new().reject_coincident(true);
+
+        assert!(strict.triangulate(&points).is_ok());
+        assert_eq!(
+            strict
+                .geometry_epsilon(4.0 * f32::EPSILON)
+                .triangulate(&points),
+            Err(TriangulatorError::CoincidentPoints(12, 30))
+        );
+    }
+
+    #[test]
+    fn hull_epsilon_keeps_jittered_boundary() {
+        let mut rng = StdRng::seed_from_u64(6);
+        let mut points = random_points(6, 40)
+            .iter()
+            .map(|p| Point::new(p.x, 1.0 + p.y))
+            .collect::<Vec<_>>();
+        for x in 0..=10 {
+            points.push(Point::new(x as f32, rng.gen_range(-1e-6..1e-6)));
+        }
+        points.push(Point::new(0.0, 11.0));
+        points.push(Point::new(10.0, 11.0));
+
+        let triangles = TriangulatorBuilder::new()
+            .hull_epsilon(1e-5)
+            .triangulate(&points)
+            .unwrap();
+
+        let triangulator = Triangulator::from_existing(triangles, &points).unwrap();
+        assert!(triangulator.validate(&points).is_ok());
+        assert!(triangulator.triangle_areas(&points).all(|a| a > 0.0));
+        assert!((triangulator.total_area(&points) - 110.0).abs() < 1e-3);
+        assert_eq!(triangulator.topology().num_vertices, points.len());
+    }
+
+    #[test]
+    fn near_points_are_merged() {
+        let mut points = random_points(5, 50);
+        points.push(points[10] + &Point::new(1e-4, -1e-4));
+
+        let triangles = TriangulatorBuilder::new()
+            .dedup_tolerance(1e-3)
+            .triangulate(&points)
+            .unwrap();
+
+        assert!(triangles
+            .iter()
+            .all(|t| ![t.index0, t.index1, t.index2].contains(&50)));
+        assert!(triangles
+            .iter()
+            .any(|t| [t.index0, t.index1, t.index2].contains(&10)));
+    }
+
+    #[test]
+    fn normalizing_keeps_far_points_triangulated() {
+        let points = random_points(9, 50);
+        let far = points
+            .iter()
+            .map(|p| *p + &Point::new(1e4, 1e4))
+            .collect::<Vec<_>>();
+
+        let triangles = TriangulatorBuilder::new()
+            .normalize_coordinates(true)
+            .insertion_order(InsertionOrder::SpatialSort)
+            .triangulate(&far)
+            .unwrap();
+
+        assert_eq!(triangles.len(), crate::triangulate(&points).unwrap().len());
+    }
+}
@@ -14,20 +14,49 @@ impl Circle {
 
     /// creates a circle with the triangle vertices on the circumference
     pub fn from_triangle(tri: &Triangle, points: &[Point]) -> Self {
-        let a = &points[tri.index0];
-        let b = &points[tri.index1];
-        let c = &points[tri.index2];
+        let a = &points[tri.index0 as usize];
+        let pos = circumcenter(
+            a,
+            &points[tri.index1 as usize],
+            &points[tri.index2 as usize],
+        );
 
-        let d = 2.0 * (a.x * (b.y - c.y) + b.x * (c.y - a.y) + c.x * (a.y - b.y));
-        let a_s = a.x * a.x + a.y * a.y;
-        let b_s = b.x * b.x + b.y * b.y;
-        let c_s = c.x * c.x + c.y * c.y;
+        let rad_sqr = (pos.x - a.x) * (pos.x - a.x) + (pos.y - a.y) * (pos.y - a.y);
+        Self::new(pos, rad_sqr)
+    }
+
+    /// creates the circle with a and b diametrically opposite, ie the smallest circle through them
+    pub fn from_diameter(a: &Point, b: &Point) -> Self {
+        let pos = Point::new(0.5 * (a.x + b.x), 0.5 * (a.y + b.y));
+        let rad_sqr = (pos.x - a.x) * (pos.x - a.x) + (pos.y - a.y) * (pos.y - a.y);
+        Self::new(pos, rad_sqr)
+    }
 
-        let circle_x = (a_s * (b.y - c.y) + b_s * (c.y - a.y) + c_s * (a.y - b.y)) / d;
-        let circle_y = (a_s * (c.x - b.x) + b_s * (a.x - c.x) + c_s * (b.x - a.x)) / d;
+    /// creates the power (orthogonal) circle of the weighted triangle vertices, where radius_sqr is the power radius.
+    /// with equal weights it is concentric with the circumcircle
+    pub(crate) fn from_triangle_weighted(
+        tri: &Triangle,
+        points: &[Point],
+        weights: &[f32],
+    ) -> Self {
+        let a = &points[tri.index0 as usize];
+        let pos = power_center(
+            (a, weights[tri.index0 as usize]),
+            (&points[tri.index1 as usize], weights[tri.index1 as usize]),
+            (&points[tri.index2 as usize], weights[tri.index2 as usize]),
+        );
 
-        let rad_sqr = (circle_x - a.x) * (circle_x - a.x) + (circle_y - a.y) * (circle_y - a.y);
-        Self::new(Point::new(circle_x, circle_y), rad_sqr)
+        let rad_sqr = (pos.x - a.x) * (pos.x - a.x) + (pos.y - a.y) * (pos.y - a.y)
+            - weights[tri.index0 as usize];
+        Self::new(pos, rad_sqr)
+    }
+
+    /// power distance test of a weighted point, for circles from from_triangle_weighted
+    pub(crate) fn contains_weighted(&self, point: &Point, weight: f32) -> bool {
+        let dx = point.x - self.pos.x;
+        let dy = point.y - self.pos.y;
+        let power = dx * dx + dy * dy - weight;
+        power <= self.radius_sqr
     }
 
     pub fn contains(&self, point: &Point) -> bool {
@@ -37,3 +66,24 @@ impl Circle {
         d_sqr <= self.radius_sqr
     }
 }
+
+/// center of the circle passing through a, b and c. non-finite if they are collinear
+pub(crate) fn circumcenter(a: &Point, b: &Point, c: &Point) -> Point {
+    power_center((a, 0.0), (b, 0.0), (c, 0.0))
+}
+
+/// point with equal power distance to the weighted points a, b and c
+fn power_center(
+    (a, weight_a): (&Point, f32),
+    (b, weight_b): (&Point, f32),
+    (c, weight_c): (&Point, f32),
+) -> Point {
+    let d = 2.0 * (a.x * (b.y - c.y) + b.x * (c.y - a.y) + c.x * (a.y - b.y));
+    let a_s = a.x * a.x + a.y * a.y - weight_a;
+    let b_s = b.x * b.x + b.y * b.y - weight_b;
+    let c_s = c.x * c.x + c.y * c.y - weight_c;
+
+    let x = (a_s * (b.y - c.y) + b_s * (c.y - a.y) + c_s * (a.y - b.y)) / d;
+    let y = (a_s * (c.x - b.x) + b_s * (a.x - c.x) + c_s * (b.x - a.x)) / d;
+    Point::new(x, y)
+}
@@ -0,0 +1,120 @@
+use crate::Point;
+
+// bits of the f32 mantissa that should be left for the in-circle test to be reliable
+const MIN_PRECISION_BITS: f32 = 12.0;
+
+/// how well the f32 geometric tests can resolve a point set, see analyze_conditioning
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Conditioning {
+    /// largest absolute coordinate
+    pub max_magnitude: f32,
+    /// larger side of the bounding box
+    pub extent: f32,
+    /// typical distance between neighbouring points, estimated as extent / sqrt(number of points)
+    pub typical_spacing: f32,
+    /// estimated bits of the 24 bit f32 mantissa lost in the in-circle test, which works on absolute
+    /// coordinates but decides on distances of about typical_spacing
+    pub precision_loss_bits: f32,
+    pub recommendation: Recommendation,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Recommendation {
+    /// enough precision is left
+    Fine,
+
+    /// the points are far from the origin compared to their extent,
+    /// moving them to the origin, eg with TriangulatorBuilder::normalize_coordinates, recovers the precision
+    NormalizeCoordinates,
+
+    /// the points are too dense for f32 even when normalized.
+    /// quantize them to integers and use triangulate_i32, which is exact
+    UseExactPredicates,
+}
+
+/// estimates how much precision the triangulation of points will lose to f32 rounding,
+/// and what to do about it. NAN and infinite coordinates are ignored
+pub fn analyze_conditioning(points: &[Point]) -> Conditioning {
+    let (mut min_x, mut min_y) = (f32::MAX, f32::MAX);
+    let (mut max_x, mut max_y) = (f32::MIN, f32::MIN);
+    let mut count = 0;
+    for p in points.iter().filter(|p| p.x.is_finite() && p.y.is_finite()) {
+        min_x = min_x.min(p.x);
+        min_y = min_y.min(p.y);
+        max_x = max_x.max(p.x);
+        max_y = max_y.max(p.y);
+        count += 1;
+    }
+
+    if count == 0 {
+        return Conditioning {
+            max_magnitude: 0.0,
+            extent: 0.0,
+            typical_spacing: 0.0,
+            precision_loss_bits: 0.0,
+            recommendation: Recommendation::Fine,
+        };
+    }
+
+    let max_magnitude = min_x
+        .abs()
+        .max(max_x.abs())
+        .max(min_y.abs())
+        .max(max_y.abs());
+    let extent = (max_x - min_x).max(max_y - min_y);
+    let typical_spacing = extent / (count as f32).sqrt();
+
+    // rounding errors are relative to the coordinates, but the test is decided on the spacing
+    let loss = |magnitude: f32| {
+        if typical_spacing > 0.0 {
+            (magnitude / typical_spacing).log2().max(0.0)
+        } else {
+            0.0
+        }
+    };
+    let precision_loss_bits = loss(max_magnitude);
+    let available = f32::MANTISSA_DIGITS as f32 - MIN_PRECISION_BITS;
+
+    let recommendation = if precision_loss_bits <= available {
+        Recommendation::Fine
+    } else if loss(0.5 * extent) <= available {
+        Recommendation::NormalizeCoordinates
+    } else {
+        Recommendation::UseExactPredicates
+    };
+
+    Conditioning {
+        max_magnitude,
+        extent,
+        typical_spacing,
+        precision_loss_bits,
+        recommendation,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn far_cluster_is_flagged() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let near = (0..100)
+            .map(|_| Point::new(rng.gen_range(0.0..1.0), rng.gen_range(0.0..1.0)))
+            .collect::<Vec<_>>();
+        let far = near
+            .iter()
+            .map(|p| *p + &Point::new(1e5, -1e5))
+            .collect::<Vec<_>>();
+
+        let near = analyze_conditioning(&near);
+        assert_eq!(near.recommendation, Recommendation::Fine);
+        assert!(near.precision_loss_bits < 5.0);
+
+        let far = analyze_conditioning(&far);
+        assert_eq!(far.recommendation, Recommendation::NormalizeCoordinates);
+        assert!(far.max_magnitude > 1e5);
+        assert!(far.precision_loss_bits > 16.0);
+    }
+}
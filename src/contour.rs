@@ -0,0 +1,94 @@
+use crate::{Point, Triangle};
+
+/// extracts the contour line where the piecewise linear field given by values (one per point) equals level.
+/// returns one line segment per crossed triangle.
+/// vertices exactly at level count as above it, so a contour running along an edge is only emitted once,
+/// and triangles lying flat at level give no segments
+pub fn contour(
+    points: &[Point],
+    triangles: &[Triangle],
+    values: &[f32],
+    level: f32,
+) -> Vec<(Point, Point)> {
+    let mut segments = Vec::new();
+
+    for tri in triangles {
+        let indices = [tri.index0, tri.index1, tri.index2];
+        let mut crossings = Vec::with_capacity(2);
+
+        for i in 0..3 {
+            let a = indices[i] as usize;
+            let b = indices[(i + 1) % 3] as usize;
+            let above_a = values[a] >= level;
+            let above_b = values[b] >= level;
+            if above_a != above_b {
+                crossings.push(crossing(
+                    &points[a], &points[b], values[a], values[b], level,
+                ));
+            }
+        }
+
+        // a plane crosses a triangle at either zero or two edges
+        if crossings.len() == 2 {
+            segments.push((crossings[0], crossings[1]));
+        }
+    }
+
+    segments
+}
+
+fn crossing(a: &Point, b: &Point, value_a: f32, value_b: f32, level: f32) -> Point {
+    let t = (level - value_a) / (value_b - value_a);
+    a + &((b - a) * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::triangulate;
+
+    #[test]
+    fn gradient_gives_straight_contour() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+            Point::new(0.7, 1.2),
+        ];
+        let values = points.iter().map(|p| p.x).collect::<Vec<_>>();
+        let triangles = triangulate(&points).unwrap();
+
+        let segments = contour(&points, &triangles, &values, 1.5);
+
+        assert!(!segments.is_empty());
+        for (a, b) in segments.iter() {
+            assert!((a.x - 1.5).abs() < 1e-5);
+            assert!((b.x - 1.5).abs() < 1e-5);
+        }
+        let length: f32 = segments.iter().map(|(a, b)| (a.y - b.y).abs()).sum();
+        assert!((length - 2.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn contour_through_vertex() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+            Point::new(1.0, 1.0),
+        ];
+        let values = points.iter().map(|p| p.x).collect::<Vec<_>>();
+        let triangles = triangulate(&points).unwrap();
+
+        let segments = contour(&points, &triangles, &values, 1.0);
+
+        let length: f32 = segments.iter().map(|(a, b)| (a.y - b.y).abs()).sum();
+        assert!((length - 2.0).abs() < 1e-5);
+        for (a, b) in segments.iter() {
+            assert!((a.x - 1.0).abs() < 1e-5);
+            assert!((b.x - 1.0).abs() < 1e-5);
+        }
+    }
+}
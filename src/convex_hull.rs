@@ -1,7 +1,11 @@
-use crate::{point::cross, Point, TriangulatorError};
+use crate::{
+    point::{cross, orient_with_tolerance, orientation, Orientation},
+    types::PointIdx,
+    Point, TriangulatorError,
+};
 use std::cmp::Ordering;
 
-// creates indices sorted on x, y secondary
+// creates indices sorted on x, y secondary, index last
 fn get_sorted_indices(points: &[Point]) -> Result<Vec<usize>, TriangulatorError> {
     let mut point_indices = points
         .iter()
@@ -20,54 +24,123 @@ fn sort_indices(point_indices: &mut [usize], points: &[Point]) -> Result<(), Tri
         }
     }
 
-    // unwraps are ok, since NAN is checked above
+    // unwraps are ok, since NAN is checked above.
+    // coincident points are ordered by index, so they are handled the same regardless of sort algorithm
     point_indices.sort_by(
         |a, b| match points[*a].x.partial_cmp(&points[*b].x).unwrap() {
             Ordering::Less => Ordering::Less,
             Ordering::Greater => Ordering::Greater,
-            Ordering::Equal => points[*a].y.partial_cmp(&points[*b].y).unwrap(),
+            Ordering::Equal => points[*a]
+                .y
+                .partial_cmp(&points[*b].y)
+                .unwrap()
+                .then(a.cmp(b)),
         },
     );
 
     Ok(())
 }
 
-pub(crate) fn convex_hull(points: &[Point]) -> Result<(Vec<usize>, Vec<usize>), TriangulatorError> {
+/// orient is the orientation predicate, cross or an exact version with the same sign convention.
+/// turns within eps of collinear, as in orient_with_tolerance, count as collinear and keep the point on the hull
+pub(crate) fn convex_hull(
+    points: &[Point],
+    orient: fn(&Point, &Point, &Point) -> f32,
+    eps: f32,
+) -> Result<(Vec<usize>, Vec<usize>), TriangulatorError> {
     // monotone chain algorithm:
     // https://en.wikibooks.org/wiki/Algorithm_Implementation/Geometry/Convex_hull/Monotone_chain
 
     let point_indices = get_sorted_indices(points)?;
-    let (mut lower, mut points_left) = half_hull(point_indices.iter(), points)?;
+    let (mut lower, mut points_left) = half_hull(point_indices.iter(), points, orient, eps)?;
+    if lower.len() < 2 {
+        // all points coincide
+        return Ok((lower, points_left));
+    }
 
     //add beginning of lower hull so upper hull will close the total hull
     points_left.push(lower[lower.len() - 1]);
     points_left.push(lower[0]);
 
     sort_indices(&mut points_left, points)?;
-    let (mut upper, points_left) = half_hull(points_left.iter().rev(), points)?;
-
-    // remove duplicate end/begin
-    upper.pop();
-    upper.remove(0);
+    let (mut upper, mut points_left) = half_hull(points_left.iter().rev(), points, orient, eps)?;
+
+    // remove duplicate end/begin. with several points at an end of lower, upper may have used another one of them
+    let (lower_end, lower_start) = (lower[lower.len() - 1], lower[0]);
+    let ends = [upper.pop().unwrap(), upper.remove(0)];
+    points_left.retain(|idx| *idx != lower_end && *idx != lower_start);
+    points_left.extend(
+        ends.iter()
+            .filter(|idx| **idx != lower_end && **idx != lower_start),
+    );
 
     lower.append(&mut upper);
     Ok((lower, points_left))
 }
 
+/// indices of the points not on the convex hull, in ascending order.
+/// duplicates of hull points are included, as only one of them is on the hull
+pub fn interior_points(points: &[Point]) -> Result<Vec<PointIdx>, TriangulatorError> {
+    let (_, points_left) = convex_hull(points, cross, 0.0)?;
+    let mut interior = points_left
+        .into_iter()
+        .map(|idx| idx as PointIdx)
+        .collect::<Vec<_>>();
+    interior.sort_unstable();
+    Ok(interior)
+}
+
+/// the corners of the convex hull in counter clockwise order, leaving out hull points on a straight stretch
+/// between two others. collinear as in orientation. for collinear points these are the two end points
+pub fn extreme_points(points: &[Point]) -> Result<Vec<PointIdx>, TriangulatorError> {
+    let (hull, _) = convex_hull(points, cross, 0.0)?;
+    let n = hull.len();
+    let corners = (0..n)
+        .filter(|&i| {
+            let (prev, current, next) = (
+                &points[hull[(i + n - 1) % n]],
+                &points[hull[i]],
+                &points[hull[(i + 1) % n]],
+            );
+            let between = (prev.x - current.x) * (next.x - current.x)
+                + (prev.y - current.y) * (next.y - current.y)
+                < 0.0;
+            !(between && orientation(prev, current, next) == Orientation::Collinear)
+        })
+        .map(|i| hull[i] as PointIdx)
+        .collect();
+    Ok(corners)
+}
+
 fn half_hull<'a, T: Iterator<Item = &'a usize>>(
     point_indices: T,
     points: &[Point],
+    orient: fn(&Point, &Point, &Point) -> f32,
+    eps: f32,
 ) -> Result<(Vec<usize>, Vec<usize>), TriangulatorError> {
-    let mut hull = Vec::new();
+    let mut hull = Vec::<usize>::new();
     let mut points_left = Vec::new();
 
     // next point should make hull 'turn' clockwise, otherwise pop point(s)
     for point_index in point_indices {
+        // a duplicate of the last hull point would make every following turn collinear, leave it inside
+        if let Some(last) = hull.last() {
+            let (p, q) = (&points[*last], &points[*point_index]);
+            if p.x == q.x && p.y == q.y {
+                if !points_left.contains(point_index) {
+                    points_left.push(*point_index);
+                }
+                continue;
+            }
+        }
+
         while hull.len() >= 2
-            && cross(
+            && orient_with_tolerance(
                 &points[hull[hull.len() - 2]],
                 &points[hull[hull.len() - 1]],
                 &points[*point_index],
+                orient,
+                eps,
             ) < 0.0
         {
             points_left.push(*hull.last().unwrap());
@@ -94,7 +167,7 @@ mod tests {
             Point::new(1.0, -0.5),
         ];
 
-        let (hull, points_left) = convex_hull(&points).unwrap();
+        let (hull, points_left) = convex_hull(&points, cross, 0.0).unwrap();
 
         assert_eq!(hull.len(), 3);
         assert_eq!(hull[0], 0);
@@ -111,7 +184,7 @@ mod tests {
             Point::new(2.0, 0.0),
         ];
 
-        let (hull, points_left) = convex_hull(&points).unwrap();
+        let (hull, points_left) = convex_hull(&points, cross, 0.0).unwrap();
 
         assert_eq!(hull.len(), 3);
         assert_eq!(hull[0], 0);
@@ -130,7 +203,7 @@ mod tests {
         ];
 
         for permution in permutations(&points) {
-            let (hull, points_left) = convex_hull(&permution).unwrap();
+            let (hull, points_left) = convex_hull(&permution, cross, 0.0).unwrap();
             assert_eq!(hull.len(), 4);
             assert_eq!(points_left.len(), 0);
         }
@@ -144,7 +217,7 @@ mod tests {
             Point::new(0.0, 2.0),
         ];
 
-        let (hull, points_left) = convex_hull(&points).unwrap();
+        let (hull, points_left) = convex_hull(&points, cross, 0.0).unwrap();
 
         assert_eq!(hull.len(), 3);
         assert_eq!(hull[0], 0);
@@ -163,7 +236,7 @@ mod tests {
         ];
 
         for permutation in permutations(&points) {
-            let (hull, points_left) = convex_hull(&permutation).unwrap();
+            let (hull, points_left) = convex_hull(&permutation, cross, 0.0).unwrap();
             assert_eq!(hull.len(), 4);
             assert_eq!(points_left.len(), 0);
         }
@@ -177,7 +250,7 @@ mod tests {
             Point::new(2.0, 2.0),
         ];
 
-        let (hull, points_left) = convex_hull(&points).unwrap();
+        let (hull, points_left) = convex_hull(&points, cross, 0.0).unwrap();
 
         assert_eq!(hull.len(), 3);
         assert_eq!(hull[0], 0);
@@ -186,6 +259,35 @@ mod tests {
         assert_eq!(points_left.len(), 0);
     }
 
+    #[test]
+    fn jittered_collinear_points_stay_on_hull() {
+        use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..20 {
+            let mut points = vec![
+                Point::new(0.0, 0.0),
+                Point::new(10.0, 0.0),
+                Point::new(10.0, 5.0),
+                Point::new(0.0, 5.0),
+                Point::new(4.0, 2.0),
+            ];
+            for x in 1..10 {
+                points.push(Point::new(x as f32, rng.gen_range(-1e-6..1e-6)));
+            }
+            points.shuffle(&mut rng);
+
+            let (hull, points_left) = convex_hull(&points, cross, 1e-5).unwrap();
+
+            assert_eq!(hull.len(), 13);
+            assert_eq!(points_left.len(), 1);
+            assert_eq!(
+                (points[points_left[0]].x, points[points_left[0]].y),
+                (4.0, 2.0)
+            );
+        }
+    }
+
     #[test]
     fn point_not_part_of_hull() {
         let points = [
@@ -195,7 +297,7 @@ mod tests {
             Point::new(0.5, 0.5),
         ];
 
-        let (hull, points_left) = convex_hull(&points).unwrap();
+        let (hull, points_left) = convex_hull(&points, cross, 0.0).unwrap();
 
         assert_eq!(hull.len(), 3);
         assert_eq!(hull[0], 0);
@@ -205,6 +307,100 @@ mod tests {
         assert_eq!(points_left[0], 3);
     }
 
+    #[test]
+    fn interior_points_of_point_not_part_of_hull() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.5, 1.0),
+            Point::new(0.5, 0.5),
+        ];
+
+        assert_eq!(interior_points(&points).unwrap(), [3]);
+        assert!(interior_points(&points[..3]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn extreme_points_of_rectangle_with_midpoints() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(4.0, 1.5),
+            Point::new(4.0, 3.0),
+            Point::new(2.0, 3.0),
+            Point::new(0.0, 3.0),
+            Point::new(0.0, 1.5),
+            Point::new(1.0, 1.0),
+        ];
+
+        let mut corners = extreme_points(&points).unwrap();
+        assert_eq!(corners.len(), 4);
+        corners.sort_unstable();
+        assert_eq!(corners, [0, 2, 4, 6]);
+
+        let line = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(2.0, 2.0),
+        ];
+        let mut ends = extreme_points(&line).unwrap();
+        ends.sort_unstable();
+        assert_eq!(ends, [0, 2]);
+    }
+
+    #[test]
+    fn duplicate_inside_point_not_part_of_hull() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.5, 1.0),
+            Point::new(0.5, 0.5),
+            Point::new(0.5, 0.5),
+        ];
+
+        let (hull, mut points_left) = convex_hull(&points, cross, 0.0).unwrap();
+        points_left.sort_unstable();
+
+        assert_eq!(hull, [0, 1, 2]);
+        assert_eq!(points_left, [3, 4]);
+    }
+
+    #[test]
+    fn duplicate_of_leftmost_point_left_once() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.5, 1.0),
+            Point::new(0.0, 0.0),
+        ];
+
+        let (mut hull, points_left) = convex_hull(&points, cross, 0.0).unwrap();
+        hull.sort_unstable();
+
+        assert_eq!(hull, [0, 1, 2]);
+        assert_eq!(points_left, [3]);
+    }
+
+    #[test]
+    fn duplicates_of_both_ends_left_once() {
+        let points = [
+            Point::new(1.0, 0.0),
+            Point::new(0.0, 0.0),
+            Point::new(0.5, 1.0),
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.0, 0.0),
+        ];
+
+        let (mut hull, mut points_left) = convex_hull(&points, cross, 0.0).unwrap();
+        hull.sort_unstable();
+        points_left.sort_unstable();
+
+        assert_eq!(hull, [0, 1, 2]);
+        assert_eq!(points_left, [3, 4, 5]);
+    }
+
     fn permutations<T: std::clone::Clone>(slice: &[T]) -> Vec<Vec<T>> {
         use itertools::Itertools;
         let perms = slice
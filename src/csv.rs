@@ -0,0 +1,69 @@
+use std::io::{BufRead, BufReader, Read};
+
+use crate::{ParseError, Point};
+
+/// reads points from columns x_col and y_col (0-based) of comma separated lines.
+/// fields are trimmed and may be wrapped in double quotes, quoted fields containing commas are not supported.
+/// blank lines are skipped, the first line is skipped if has_header is set.
+/// a line with a missing or non-numeric field gives InvalidLine with its 1-based line number
+pub fn read_points_csv<R: Read>(
+    r: R,
+    x_col: usize,
+    y_col: usize,
+    has_header: bool,
+) -> Result<Vec<Point>, ParseError> {
+    let mut points = Vec::new();
+
+    for (line_idx, line) in BufReader::new(r).lines().enumerate() {
+        let line = line?;
+        if (has_header && line_idx == 0) || line.trim().is_empty() {
+            continue;
+        }
+
+        let fields = line.split(',').collect::<Vec<_>>();
+        let parse = |col: usize| {
+            fields
+                .get(col)
+                .map(|field| field.trim().trim_matches('"').trim())
+                .and_then(|field| field.parse::<f32>().ok())
+                .ok_or(ParseError::InvalidLine(line_idx + 1))
+        };
+        points.push(Point::new(parse(x_col)?, parse(y_col)?));
+    }
+
+    Ok(points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_columns() {
+        let csv = "id,name,lon,lat
+1,a,12.5,-3
+2,\"b\", 13.25 ,\"4.5\"
+
+3,c,1e2,0
+";
+
+        let points = read_points_csv(csv.as_bytes(), 2, 3, true).unwrap();
+
+        let coords = points.iter().map(|p| (p.x, p.y)).collect::<Vec<_>>();
+        assert_eq!(coords, [(12.5, -3.0), (13.25, 4.5), (100.0, 0.0)]);
+    }
+
+    #[test]
+    fn invalid_line_is_reported() {
+        let csv = "1,2\n3,x\n";
+
+        assert!(matches!(
+            read_points_csv(csv.as_bytes(), 0, 1, false),
+            Err(ParseError::InvalidLine(2))
+        ));
+        assert!(matches!(
+            read_points_csv(csv.as_bytes(), 0, 2, false),
+            Err(ParseError::InvalidLine(1))
+        ));
+    }
+}
@@ -0,0 +1,116 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use crate::{
+    delaunay_inc::{triangulate_star_polygon, DelaunayIncremental},
+    interpolation,
+    types::PointIdx,
+    Point,
+};
+
+/// removes interior vertices, least important first, until target_count points are used by the triangulation
+/// or only boundary vertices are left. removing a vertex changes the importance of its neighbours only.
+/// the importance is the height error removing it would cause if heights are given, otherwise the distance to its
+/// nearest neighbour, which thins out dense areas first. afterwards points, and heights, only hold the used points
+pub(crate) fn decimate(
+    triangulator: &mut DelaunayIncremental,
+    points: &mut Vec<Point>,
+    mut heights: Option<&mut Vec<f32>>,
+    target_count: usize,
+) {
+    let mut used = vec![false; points.len()];
+    for tri in triangulator.get_triangles() {
+        for idx in [tri.index0, tri.index1, tri.index2] {
+            used[idx as usize] = true;
+        }
+    }
+    let mut num_used = used.iter().filter(|used| **used).count();
+
+    // errors are non negative, so their bits sort like them. outdated entries are skipped by their version
+    let mut versions = vec![0_u32; points.len()];
+    let mut queue = BinaryHeap::new();
+    let heights_ref = heights.as_deref().map(|h| h.as_slice());
+    for vertex in 0..points.len() as PointIdx {
+        if let Some(error) = removal_error(triangulator, points, heights_ref, vertex) {
+            queue.push(Reverse((error.to_bits(), vertex, 0)));
+        }
+    }
+
+    while num_used > target_count {
+        let Reverse((_, vertex, version)) = match queue.pop() {
+            Some(entry) => entry,
+            None => break,
+        };
+        if version != versions[vertex as usize] || !used[vertex as usize] {
+            continue;
+        }
+
+        let ring = triangulator.one_ring(vertex);
+        if !triangulator.remove_point(vertex, points) {
+            continue;
+        }
+        used[vertex as usize] = false;
+        num_used -= 1;
+
+        for neighbour in ring {
+            versions[neighbour as usize] += 1;
+            if let Some(error) = removal_error(triangulator, points, heights_ref, neighbour) {
+                queue.push(Reverse((
+                    error.to_bits(),
+                    neighbour,
+                    versions[neighbour as usize],
+                )));
+            }
+        }
+    }
+
+    let mut new_index = vec![None; points.len()];
+    let mut num_kept = 0;
+    for (idx, new_idx) in new_index.iter_mut().enumerate() {
+        if used[idx] {
+            *new_idx = Some(num_kept as PointIdx);
+            num_kept += 1;
+        }
+    }
+    triangulator.renumber_points(|idx| new_index[idx as usize]);
+
+    let mut idx = 0;
+    points.retain(|_| {
+        idx += 1;
+        used[idx - 1]
+    });
+    if let Some(heights) = heights.as_mut() {
+        let mut idx = 0;
+        heights.retain(|_| {
+            idx += 1;
+            used[idx - 1]
+        });
+    }
+}
+
+// None if vertex can't be removed, ie it's on the boundary or unused
+fn removal_error(
+    triangulator: &DelaunayIncremental,
+    points: &[Point],
+    heights: Option<&[f32]>,
+    vertex: PointIdx,
+) -> Option<f32> {
+    let ring = triangulator.ordered_ring(vertex, points)?;
+    let point = &points[vertex as usize];
+
+    let error = match heights {
+        Some(heights) => {
+            let filled = triangulate_star_polygon(&ring, points)?;
+            let height = interpolation::linear(&filled, points, heights, point)?;
+            (height - heights[vertex as usize]).abs()
+        }
+        None => ring
+            .iter()
+            .map(|idx| point.distance(&points[*idx as usize]))
+            .fold(f32::INFINITY, f32::min),
+    };
+    if error.is_nan() {
+        None
+    } else {
+        Some(error)
+    }
+}
@@ -1,34 +1,304 @@
-use std::collections::HashSet;
-
 use crate::{
+    boundary::boundary_polygon,
     circle::Circle,
     convex_hull::convex_hull,
-    edge::Edge,
-    point::cross,
-    types::{PointIdx, TriIdx},
-    Point, Triangle, TriangulatorError,
+    edge::{DirectedEdge, Edge},
+    exact,
+    insertion_order::{order_indices, shuffle, InsertionOrder},
+    point::{cross, orient_with_tolerance},
+    polygon::signed_area,
+    types::{HashMap, HashSet, Neighbours, PointIdx, TriIdx},
+    CollapseError, FlipCriterion, FlipError, FullTriangulation, Point, Topology, Triangle,
+    Triangulation, TriangulatorError, TriangulatorOptions, ValidationIssue,
 };
 
-mod ordered_pair;
-mod tri_edge_mapping;
+pub(crate) mod tri_edge_mapping;
+
+use std::{cmp::Reverse, collections::BinaryHeap};
 
-use ordered_pair::OrderedPair;
 use tri_edge_mapping::TriangleEdgeMapping;
 
 pub(crate) fn triangulate(points: &[Point]) -> Result<Vec<Triangle>, TriangulatorError> {
+    let mut triangles = Vec::new();
+    triangulate_into(points, &mut triangles)?;
+    Ok(triangles)
+}
+
+pub(crate) fn triangulate_weighted(
+    points: &[Point],
+    weights: &[f32],
+) -> Result<Vec<Triangle>, TriangulatorError> {
+    let mut triangulator = DelaunayIncremental::new();
+    triangulator.weights = weights.to_vec();
+    triangulator.initial_triangulation(points)?;
+    triangulator.add_all(points)?;
+
+    Ok(triangulator.triangles)
+}
+
+/// exact triangulation of integer points, see lib.rs
+pub(crate) fn triangulate_i32(points: &[(i32, i32)]) -> Result<Vec<Triangle>, TriangulatorError> {
+    let limit = exact::MAX_EXACT_COORD;
+    let mut float_points = Vec::with_capacity(points.len());
+    for (i, &(x, y)) in points.iter().enumerate() {
+        if !(-limit..=limit).contains(&x) || !(-limit..=limit).contains(&y) {
+            return Err(TriangulatorError::CoordinateOutOfRange(i));
+        }
+        float_points.push(Point::new(x as f32, y as f32));
+    }
+
+    let mut triangulator = DelaunayIncremental::new();
+    triangulator.exact = true;
+    triangulator.initial_triangulation(&float_points)?;
+    triangulator.add_all(&float_points)?;
+
+    Ok(triangulator.triangles)
+}
+
+pub(crate) fn triangulate_into(
+    points: &[Point],
+    out: &mut Vec<Triangle>,
+) -> Result<(), TriangulatorError> {
+    // let the triangulator work directly in the callers buffer
+    let mut triangulator = DelaunayIncremental::new();
+    triangulator.triangles = std::mem::take(out);
+    let res = triangulator
+        .initial_triangulation(points)
+        .map(|_| ())
+        .and_then(|_| triangulator.add_all(points));
+
+    *out = triangulator.triangles;
+    res
+}
+
+pub(crate) fn triangulate_full(points: &[Point]) -> Result<FullTriangulation, TriangulatorError> {
+    let mut triangulator = DelaunayIncremental::new();
+    triangulator.initial_triangulation(points)?;
+    triangulator.add_all(points)?;
+
+    let mut edges = Vec::with_capacity(triangulator.triangles.len() * 3 / 2 + 3);
+    let mut boundary = Vec::new();
+    for edge in triangulator.tri_edge_mapping.edges() {
+        edges.push(*edge);
+        if triangulator.tri_edge_mapping.is_boundary(edge) {
+            boundary.push(*edge);
+        }
+    }
+    edges.sort_unstable();
+    boundary.sort_unstable();
+
+    let neighbours = triangulator.neighbours();
+    Ok(FullTriangulation {
+        triangles: triangulator.triangles,
+        edges,
+        boundary,
+        neighbours,
+    })
+}
+
+/// joins two triangulations sharing boundary edges, then fills the gaps along the seam and flips to delaunay
+pub(crate) fn merge(
+    a: Triangulation,
+    b: Triangulation,
+    points: &[Point],
+) -> Result<Triangulation, TriangulatorError> {
+    let mut triangles = Vec::with_capacity(a.triangles.len() + b.triangles.len());
+    for part in [&a, &b].iter() {
+        let to_input = |idx: PointIdx| part.index_map.get(idx as usize).copied();
+        for tri in part.triangles.iter() {
+            match (
+                to_input(tri.index0),
+                to_input(tri.index1),
+                to_input(tri.index2),
+            ) {
+                (Some(i0), Some(i1), Some(i2)) => triangles.push(Triangle::new(i0, i1, i2)),
+                _ => return Err(TriangulatorError::InvalidMesh),
+            }
+        }
+    }
+
+    // the merged triangles index the points used by either, in input order
+    let mut index_map = a
+        .index_map
+        .iter()
+        .chain(b.index_map.iter())
+        .copied()
+        .collect::<Vec<_>>();
+    index_map.sort_unstable();
+    index_map.dedup();
+    if index_map
+        .last()
+        .is_some_and(|idx| *idx as usize >= points.len())
+    {
+        return Err(TriangulatorError::InvalidMesh);
+    }
+    let to_merged = index_map
+        .iter()
+        .enumerate()
+        .map(|(merged_idx, input_idx)| (*input_idx, merged_idx as PointIdx))
+        .collect::<HashMap<_, _>>();
+    for tri in triangles.iter_mut() {
+        *tri = Triangle::new(
+            to_merged[&tri.index0],
+            to_merged[&tri.index1],
+            to_merged[&tri.index2],
+        );
+    }
+    let merged_points = index_map
+        .iter()
+        .map(|idx| points[*idx as usize])
+        .collect::<Vec<_>>();
+
+    // overlapping parts share edges between more than two triangles, which the mapping can't hold
+    let mut edge_count = HashMap::<Edge, usize>::default();
+    for tri in triangles.iter() {
+        for edge in [
+            Edge::new(tri.index0, tri.index1),
+            Edge::new(tri.index1, tri.index2),
+            Edge::new(tri.index2, tri.index0),
+        ] {
+            *edge_count.entry(edge).or_insert(0) += 1;
+        }
+    }
+    if edge_count.values().any(|count| *count > 2) {
+        return Err(TriangulatorError::InvalidMesh);
+    }
+
+    let mut triangulator = DelaunayIncremental::from_existing(triangles, &merged_points)?;
+    // without a shared edge the boundary is two loops, and the gap between them can't be filled
+    if triangulator.topology().num_components != 1 {
+        return Err(TriangulatorError::InvalidMesh);
+    }
+    triangulator.convexify(&merged_points);
+    let check_stack = (0..triangulator.triangles.len() as TriIdx).collect();
+    triangulator.flip_pairs(check_stack, &merged_points);
+    if triangulator.flip_limit_exceeded {
+        return Err(TriangulatorError::FlipLimitExceeded);
+    }
+
+    Ok(Triangulation {
+        triangles: triangulator.triangles,
+        index_map,
+    })
+}
+
+pub(crate) fn triangulate_with_neighbours(
+    points: &[Point],
+) -> Result<(Vec<Triangle>, Vec<Neighbours>), TriangulatorError> {
     let mut triangulator = DelaunayIncremental::new();
-    let _ = triangulator.initial_triangulation(points)?;
+    triangulator.initial_triangulation(points)?;
+    triangulator.add_all(points)?;
+
+    let neighbours = triangulator.neighbours();
+    Ok((triangulator.triangles, neighbours))
+}
+
+/// delaunay triangulation of the polygon, without computing its hull
+pub(crate) fn triangulate_convex_polygon(
+    points: &[Point],
+) -> Result<Vec<Triangle>, TriangulatorError> {
+    if let Some(i) = points.iter().position(|p| p.x.is_nan() || p.y.is_nan()) {
+        return Err(TriangulatorError::NANInInput(i));
+    }
 
-    while triangulator.do_step(points) {}
+    let polygon = (0..points.len() as PointIdx).collect::<Vec<_>>();
+    let mut triangulator = DelaunayIncremental::with_capacity(points.len());
+    triangulator.triangulate_convex(&polygon, points);
+    if triangulator.flip_limit_exceeded {
+        return Err(TriangulatorError::FlipLimitExceeded);
+    }
+    Ok(triangulator.triangles)
+}
 
+/// bowyer-watson: starts from a triangle enclosing all points, inserts every point, then removes the enclosing triangle's vertices
+pub(crate) fn triangulate_bowyer_watson(
+    points: &[Point],
+) -> Result<Vec<Triangle>, TriangulatorError> {
+    for (i, p) in points.iter().enumerate() {
+        if p.x.is_nan() || p.y.is_nan() {
+            return Err(TriangulatorError::NANInInput(i));
+        }
+    }
+
+    let num_points = points.len() as PointIdx;
+    let mut extended_points = points.to_vec();
+    extended_points.extend_from_slice(&super_triangle(points));
+
+    let mut triangulator = DelaunayIncremental::with_capacity(extended_points.len());
+    triangulator
+        .triangles
+        .push(Triangle::new(num_points, num_points + 1, num_points + 2));
+    triangulator
+        .tri_edge_mapping
+        .add_triangle(0, &triangulator.triangles);
+    triangulator.points_to_add = (0..num_points).rev().collect();
+
+    triangulator.add_all(&extended_points)?;
+
+    triangulator.retain_triangles(|tri| {
+        tri.index0 < num_points && tri.index1 < num_points && tri.index2 < num_points
+    });
+    triangulator.convexify(points);
+    if triangulator.flip_limit_exceeded {
+        return Err(TriangulatorError::FlipLimitExceeded);
+    }
     Ok(triangulator.triangles)
 }
 
+// a triangle well outside of the bounding box of points
+fn super_triangle(points: &[Point]) -> [Point; 3] {
+    let (mut min_x, mut min_y) = (f32::MAX, f32::MAX);
+    let (mut max_x, mut max_y) = (f32::MIN, f32::MIN);
+    for p in points {
+        min_x = min_x.min(p.x);
+        min_y = min_y.min(p.y);
+        max_x = max_x.max(p.x);
+        max_y = max_y.max(p.y);
+    }
+
+    let size = (max_x - min_x).max(max_y - min_y).max(1.0);
+    let center_x = 0.5 * (min_x + max_x);
+    let center_y = 0.5 * (min_y + max_y);
+
+    [
+        Point::new(center_x - 20.0 * size, center_y - size),
+        Point::new(center_x + 20.0 * size, center_y - size),
+        Point::new(center_x, center_y + 20.0 * size),
+    ]
+}
+
 #[derive(Default, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) struct DelaunayIncremental {
     pub triangles: Vec<Triangle>,
     points_to_add: Vec<PointIdx>,
     tri_edge_mapping: TriangleEdgeMapping,
+    pub insertion_order: InsertionOrder,
+    pub options: TriangulatorOptions,
+    // per point weights for a weighted (power) triangulation, empty for an ordinary one
+    pub weights: Vec<f32>,
+    // integral coordinates, use exact predicates
+    pub exact: bool,
+    // (skipped point, vertex it coincides with)
+    pub merged: Vec<(PointIdx, PointIdx)>,
+    // from initial_triangulation, for reset_to_hull. the interior points in insertion order, last first
+    hull: Vec<PointIdx>,
+    hull_interior: Vec<PointIdx>,
+    // set if flip_pairs gave up, the mesh is then valid but maybe not delaunay
+    #[cfg_attr(feature = "serde", serde(skip))]
+    flip_limit_exceeded: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    history: Vec<StepRecord>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    recording: Option<StepRecord>,
+}
+
+/// what a step changed, so that it can be undone
+#[derive(Default, Clone, Debug)]
+struct StepRecord {
+    point_idx: PointIdx,
+    num_triangles: usize,
+    replaced: Vec<(TriIdx, Triangle)>,
 }
 
 impl DelaunayIncremental {
@@ -37,71 +307,885 @@ impl DelaunayIncremental {
             triangles: Vec::new(),
             points_to_add: Vec::new(),
             tri_edge_mapping: TriangleEdgeMapping::new(),
+            insertion_order: InsertionOrder::default(),
+            options: TriangulatorOptions::default(),
+            weights: Vec::new(),
+            exact: false,
+            merged: Vec::new(),
+            hull: Vec::new(),
+            hull_interior: Vec::new(),
+            flip_limit_exceeded: false,
+            history: Vec::new(),
+            recording: None,
         }
     }
 
+    pub(crate) fn with_capacity(num_points: usize) -> Self {
+        // a triangulation of n points has at most 2n - 5 triangles
+        let num_triangles = 2 * num_points;
+        Self {
+            triangles: Vec::with_capacity(num_triangles),
+            points_to_add: Vec::with_capacity(num_points),
+            tri_edge_mapping: TriangleEdgeMapping::with_capacity(num_triangles),
+            insertion_order: InsertionOrder::default(),
+            options: TriangulatorOptions::default(),
+            weights: Vec::new(),
+            exact: false,
+            merged: Vec::new(),
+            hull: Vec::new(),
+            hull_interior: Vec::new(),
+            flip_limit_exceeded: false,
+            history: Vec::new(),
+            recording: None,
+        }
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.triangles.clear();
+        self.points_to_add.clear();
+        self.tri_edge_mapping.clear();
+        self.history.clear();
+        self.merged.clear();
+        self.hull.clear();
+        self.hull_interior.clear();
+        self.flip_limit_exceeded = false;
+    }
+
     pub(crate) fn initial_triangulation(
         &mut self,
         points: &[Point],
     ) -> Result<&[Triangle], TriangulatorError> {
-        let (hull, points_inside_hull) = convex_hull(points)?;
-        self.points_to_add = points_inside_hull;
+        let (hull, points_inside_hull) = convex_hull(points, self.orient(), self.hull_epsilon())?;
+        let hull = hull.into_iter().map(|i| i as PointIdx).collect::<Vec<_>>();
 
-        self.triangles = generate_triangles_from_hull(&hull);
-        for i in 0..self.triangles.len() {
-            self.tri_edge_mapping.add_triangle(i, &self.triangles);
+        // the hull of collinear points is the line, whose fan would be zero area triangles
+        if hull.len() < 3 {
+            return Err(TriangulatorError::Collinear);
         }
-
-        if self.points_to_add.is_empty() {
-            let check_stack = (0..self.triangles.len()).collect();
-            self.flip_pairs(check_stack, points);
+        let (a, b) = (&points[hull[0] as usize], &points[hull[1] as usize]);
+        let (orient, eps) = (self.orient(), self.geometry_epsilon());
+        if hull
+            .iter()
+            .all(|idx| orient_with_tolerance(b, &points[*idx as usize], a, orient, eps) == 0.0)
+        {
+            return Err(TriangulatorError::Collinear);
         }
+        let mut interior = points_inside_hull
+            .into_iter()
+            .map(|i| i as PointIdx)
+            .collect::<Vec<_>>();
+
+        // points are popped from the back
+        order_indices(&mut interior, points, self.insertion_order);
+        interior.reverse();
 
+        self.hull = hull;
+        self.hull_interior = interior;
+        self.triangulate_hull(points)?;
         Ok(self.get_triangles())
     }
 
+    /// back to the state after initial_triangulation, with the same hull and order of points to add.
+    /// false, with nothing changed, if there was no initial_triangulation, eg for from_existing
+    pub(crate) fn reset_to_hull(&mut self, points: &[Point]) -> bool {
+        // the flips of the hull succeeded in initial_triangulation, so they do again for the same points
+        !self.hull.is_empty() && self.triangulate_hull(points).is_ok()
+    }
+
+    // triangulates the saved hull, to be delaunay, and sets the saved interior points to be added
+    fn triangulate_hull(&mut self, points: &[Point]) -> Result<(), TriangulatorError> {
+        self.points_to_add.clone_from(&self.hull_interior);
+        self.history.clear();
+        self.merged.clear();
+
+        self.triangles.clear();
+        self.tri_edge_mapping.clear();
+        self.flip_limit_exceeded = false;
+        // with a tolerance the hull can be concave within it, where a fan could fold over
+        let clipped = if self.hull_epsilon() > 0.0 {
+            triangulate_star_polygon(&self.hull, points)
+        } else {
+            None
+        };
+        match clipped {
+            Some(triangles) => {
+                self.triangles.extend(triangles);
+                for i in 0..self.triangles.len() {
+                    self.tri_edge_mapping
+                        .add_triangle(i as TriIdx, &self.triangles);
+                }
+
+                // the hull must be delaunay before inserting points, since later flips only reach changed triangles
+                let check_stack = (0..self.triangles.len() as TriIdx).collect();
+                self.flip_pairs(check_stack, points);
+            }
+            None => {
+                let hull = std::mem::take(&mut self.hull);
+                self.triangulate_convex(&hull, points);
+                self.hull = hull;
+            }
+        }
+        if self.flip_limit_exceeded {
+            return Err(TriangulatorError::FlipLimitExceeded);
+        }
+        Ok(())
+    }
+
+    // delaunay triangulation of a counter clockwise convex polygon, by chew's algorithm: vertices are removed in
+    // random order, then added back in reverse, each as a triangle on the edge between its neighbours, followed by flips.
+    // that takes an expected constant number of flips per vertex, while flipping a fan can take a quadratic number
+    fn triangulate_convex(&mut self, polygon: &[PointIdx], points: &[Point]) {
+        let n = polygon.len();
+        let mut order = (0..n as PointIdx).collect::<Vec<_>>();
+        shuffle(&mut order, n as u64);
+
+        let mut prev = (0..n).map(|i| (i + n - 1) % n).collect::<Vec<_>>();
+        let mut next = (0..n).map(|i| (i + 1) % n).collect::<Vec<_>>();
+        let mut removed = Vec::with_capacity(n);
+        for i in order.iter().take(n - 3).map(|i| *i as usize) {
+            removed.push((prev[i], i, next[i]));
+            next[prev[i]] = next[i];
+            prev[next[i]] = prev[i];
+        }
+        let last = order[n - 3] as usize;
+        removed.push((prev[last], last, next[last]));
+
+        for (before, i, after) in removed.into_iter().rev() {
+            self.triangles
+                .push(Triangle::new(polygon[before], polygon[i], polygon[after]));
+            let tri_idx = (self.triangles.len() - 1) as TriIdx;
+            self.tri_edge_mapping.add_triangle(tri_idx, &self.triangles);
+            self.flip_pairs(vec![tri_idx], points);
+            if self.flip_limit_exceeded {
+                return;
+            }
+        }
+    }
+
+    fn hull_epsilon(&self) -> f32 {
+        if self.exact {
+            0.0
+        } else {
+            self.options.hull_epsilon
+        }
+    }
+
+    fn orient(&self) -> fn(&Point, &Point, &Point) -> f32 {
+        if self.exact {
+            exact::orient
+        } else {
+            cross
+        }
+    }
+
+    // the exact predicates have no rounding to tolerate
+    fn geometry_epsilon(&self) -> f32 {
+        if self.exact {
+            0.0
+        } else {
+            self.options.geometry_epsilon
+        }
+    }
+
     pub(crate) fn get_triangles(&self) -> &[Triangle] {
         self.triangles.as_slice()
     }
 
-    pub(crate) fn do_step(&mut self, points: &[Point]) -> bool {
-        if let Some(point_idx) = self.points_to_add.pop() {
-            self.add_point(point_idx, points);
-            true
+    /// fills concavities along the boundary with triangles, then restores the delaunay property.
+    /// needed after removing a super triangle, since triangles along the hull can be lost with it
+    fn convexify(&mut self, points: &[Point]) {
+        let mut boundary = boundary_polygon(&self.triangles);
+        if signed_area(&boundary, points) < 0.0 {
+            boundary.reverse();
+        }
+
+        let mut changed_triangles = Vec::new();
+        let mut i = 0;
+        while boundary.len() > 3 && i < boundary.len() {
+            let a = boundary[i];
+            let b = boundary[(i + 1) % boundary.len()];
+            let c = boundary[(i + 2) % boundary.len()];
+
+            // a right turn on a counter clockwise boundary is a concavity
+            if cross(
+                &points[c as usize],
+                &points[a as usize],
+                &points[b as usize],
+            ) < 0.0
+            {
+                self.triangles.push(Triangle::new(a, b, c));
+                let tri_idx = (self.triangles.len() - 1) as TriIdx;
+                self.tri_edge_mapping.add_triangle(tri_idx, &self.triangles);
+                changed_triangles.push(tri_idx);
+
+                boundary.remove((i + 1) % boundary.len());
+                i = i.saturating_sub(1);
+            } else {
+                i += 1;
+            }
+        }
+
+        self.flip_pairs(changed_triangles, points);
+    }
+
+    /// keeps only the triangles for which keep returns true, rebuilding the edge mapping
+    pub(crate) fn retain_triangles<F: FnMut(&Triangle) -> bool>(&mut self, keep: F) {
+        self.triangles.retain(keep);
+        self.history.clear();
+
+        self.tri_edge_mapping.clear();
+        for i in 0..self.triangles.len() {
+            self.tri_edge_mapping
+                .add_triangle(i as TriIdx, &self.triangles);
+        }
+    }
+
+    /// for each point, the points sharing an edge with it, and whether it is on the boundary
+    pub(crate) fn vertex_adjacency(&self, num_points: usize) -> (Vec<Vec<PointIdx>>, Vec<bool>) {
+        let mut neighbours = vec![Vec::new(); num_points];
+        let mut on_boundary = vec![false; num_points];
+
+        for edge in self.tri_edge_mapping.edges() {
+            neighbours[edge.index_0 as usize].push(edge.index_1);
+            neighbours[edge.index_1 as usize].push(edge.index_0);
+            if self.tri_edge_mapping.is_boundary(edge) {
+                on_boundary[edge.index_0 as usize] = true;
+                on_boundary[edge.index_1 as usize] = true;
+            }
+        }
+
+        (neighbours, on_boundary)
+    }
+
+    pub(crate) fn one_ring(&self, vertex: PointIdx) -> Vec<PointIdx> {
+        let mut ring = Vec::new();
+        for tri_idx in self.tri_edge_mapping.get_vertex_triangles(vertex) {
+            let tri = &self.triangles[tri_idx as usize];
+            for idx in [tri.index0, tri.index1, tri.index2] {
+                if idx != vertex && !ring.contains(&idx) {
+                    ring.push(idx);
+                }
+            }
+        }
+        ring.sort_unstable();
+        ring
+    }
+
+    /// best first search through the vertex adjacency, starting at the vertices of start_triangle
+    pub(crate) fn k_nearest(
+        &self,
+        points: &[Point],
+        query: &Point,
+        start_triangle: TriIdx,
+        k: usize,
+    ) -> Vec<PointIdx> {
+        // squared distances are never negative, so their bits sort the same as their values
+        let dist_key = |idx: PointIdx| {
+            let d = points[idx as usize] - query;
+            (d.x * d.x + d.y * d.y).to_bits()
+        };
+
+        let mut frontier = BinaryHeap::new();
+        let mut seen = HashSet::<PointIdx>::default();
+        let tri = &self.triangles[start_triangle as usize];
+        for idx in [tri.index0, tri.index1, tri.index2] {
+            seen.insert(idx);
+            frontier.push(Reverse((dist_key(idx), idx)));
+        }
+
+        let mut nearest = Vec::with_capacity(k);
+        while let Some(Reverse((_, idx))) = frontier.pop() {
+            if nearest.len() == k {
+                break;
+            }
+            nearest.push(idx);
+            for neighbour in self.one_ring(idx) {
+                if seen.insert(neighbour) {
+                    frontier.push(Reverse((dist_key(neighbour), neighbour)));
+                }
+            }
+        }
+        nearest
+    }
+
+    /// merges triangles into convex polygons, hertel-mehlhorn style: interior edges are removed, longest first,
+    /// whenever the polygons on both sides stay convex together. polygons are counter clockwise
+    pub(crate) fn convex_partition(&self, points: &[Point]) -> Vec<Vec<PointIdx>> {
+        let mut polygons = self
+            .triangles
+            .iter()
+            .map(|tri| {
+                let mut polygon = vec![tri.index0, tri.index1, tri.index2];
+                if signed_area(&polygon, points) < 0.0 {
+                    polygon.reverse();
+                }
+                Some(polygon)
+            })
+            .collect::<Vec<_>>();
+        // polygon each triangle has been merged into
+        let mut owner = (0..self.triangles.len()).collect::<Vec<_>>();
+
+        let length_sqr = |edge: &Edge| {
+            let d = points[edge.index_0 as usize] - points[edge.index_1 as usize];
+            d.x * d.x + d.y * d.y
+        };
+        let mut interior = self
+            .tri_edge_mapping
+            .edges()
+            .filter(|edge| !self.tri_edge_mapping.is_boundary(edge))
+            .copied()
+            .collect::<Vec<_>>();
+        interior.sort_unstable();
+        interior.sort_by(|a, b| {
+            length_sqr(b)
+                .partial_cmp(&length_sqr(a))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        for edge in interior {
+            let tris = self.tri_edge_mapping.get_triangles(&edge);
+            if tris.len() != 2 {
+                continue;
+            }
+            let (p0, p1) = (owner[tris[0] as usize], owner[tris[1] as usize]);
+            if p0 == p1 {
+                continue;
+            }
+
+            let merged = match (&polygons[p0], &polygons[p1]) {
+                (Some(a), Some(b)) => merge_along_edge(a, b, &edge),
+                _ => None,
+            };
+            if let Some(merged) = merged.filter(|merged| is_convex(merged, points)) {
+                polygons[p0] = Some(merged);
+                polygons[p1] = None;
+                for o in owner.iter_mut().filter(|o| **o == p1) {
+                    *o = p0;
+                }
+            }
+        }
+
+        polygons.into_iter().flatten().collect()
+    }
+
+    pub(crate) fn is_boundary_edge(&self, edge: &Edge) -> bool {
+        self.tri_edge_mapping.is_boundary(edge)
+    }
+
+    pub(crate) fn triangles_of_edge(&self, edge: &Edge) -> Vec<TriIdx> {
+        let mut tris = self.tri_edge_mapping.get_triangles(edge);
+        tris.sort_unstable();
+        tris
+    }
+
+    /// (shortest, longest) edge, the lower edge of equally long ones
+    pub(crate) fn edge_length_extremes(&self, points: &[Point]) -> Option<(Edge, Edge)> {
+        let length =
+            |edge: &Edge| points[edge.index_0 as usize].distance(&points[edge.index_1 as usize]);
+        let by_length = |a: &&Edge, b: &&Edge| length(a).total_cmp(&length(b)).then(a.cmp(b));
+        let by_length_rev = |a: &&Edge, b: &&Edge| length(a).total_cmp(&length(b)).then(b.cmp(a));
+
+        let shortest = self.tri_edge_mapping.edges().min_by(by_length)?;
+        let longest = self.tri_edge_mapping.edges().max_by(by_length_rev)?;
+        Some((*shortest, *longest))
+    }
+
+    /// interior edges failing the empty circumcircle test that flip_pairs uses, sorted
+    pub(crate) fn non_delaunay_edges(&self, points: &[Point]) -> Vec<Edge> {
+        let mut edges = self
+            .tri_edge_mapping
+            .edges()
+            .filter(|edge| {
+                let tris = self.tri_edge_mapping.get_triangles(edge);
+                tris.len() == 2
+                    && should_flip(
+                        &self.triangles[tris[0] as usize],
+                        &self.triangles[tris[1] as usize],
+                        points,
+                        &self.weights,
+                        self.exact,
+                        FlipCriterion::Delaunay,
+                    )
+            })
+            .copied()
+            .collect::<Vec<_>>();
+        edges.sort_unstable();
+        edges
+    }
+
+    pub(crate) fn opposite_triangle(&self, tri_idx: TriIdx, edge: &Edge) -> Option<TriIdx> {
+        let tris = self.tri_edge_mapping.get_triangles(edge);
+        if !tris.contains(&tri_idx) {
+            return None;
+        }
+        tris.into_iter().find(|idx| *idx != tri_idx)
+    }
+
+    pub(crate) fn triangles_around(&self, vertex: PointIdx) -> Vec<TriIdx> {
+        let mut tris = self.tri_edge_mapping.get_vertex_triangles(vertex);
+        tris.sort_unstable();
+        tris
+    }
+
+    pub(crate) fn triangle_vertex_neighbours(&self, tri_idx: TriIdx) -> Vec<TriIdx> {
+        let tri = &self.triangles[tri_idx as usize];
+        let mut tris = [tri.index0, tri.index1, tri.index2]
+            .iter()
+            .flat_map(|vertex| self.tri_edge_mapping.get_vertex_triangles(*vertex))
+            .filter(|idx| *idx != tri_idx)
+            .collect::<Vec<_>>();
+        tris.sort_unstable();
+        tris.dedup();
+        tris
+    }
+
+    pub(crate) fn dual_graph(&self) -> Vec<Vec<TriIdx>> {
+        (0..self.triangles.len() as TriIdx)
+            .map(|tri| {
+                let mut neighbours = self.tri_edge_mapping.neighbouring_triangles(tri);
+                neighbours.sort_unstable();
+                neighbours
+            })
+            .collect()
+    }
+
+    pub(crate) fn topology(&self) -> Topology {
+        let num_vertices = self.tri_edge_mapping.num_vertices();
+        let num_edges = self.tri_edge_mapping.num_edges();
+        let num_triangles = self.triangles.len();
+
+        let mut visited = vec![false; num_triangles];
+        let mut num_components = 0;
+        for start in 0..num_triangles {
+            if visited[start] {
+                continue;
+            }
+            num_components += 1;
+            visited[start] = true;
+            let mut stack = vec![start as TriIdx];
+            while let Some(tri) = stack.pop() {
+                self.tri_edge_mapping.for_each_neighbour(tri, |neighbour| {
+                    if !visited[neighbour as usize] {
+                        visited[neighbour as usize] = true;
+                        stack.push(neighbour);
+                    }
+                });
+            }
+        }
+
+        Topology {
+            num_vertices,
+            num_edges,
+            num_triangles,
+            num_components,
+            euler_characteristic: num_vertices as i64 - num_edges as i64 + num_triangles as i64,
+        }
+    }
+
+    /// neighbours[t][e] is the triangle across edge e of triangle t, None on the hull.
+    /// edge 0 is index0-index1, edge 1 is index1-index2 and edge 2 is index2-index0
+    pub(crate) fn neighbours(&self) -> Vec<Neighbours> {
+        self.triangles
+            .iter()
+            .enumerate()
+            .map(|(tri_idx, tri)| {
+                let across = |a: PointIdx, b: PointIdx| {
+                    self.tri_edge_mapping
+                        .get_triangles(&Edge::new(a, b))
+                        .into_iter()
+                        .find(|other| *other as usize != tri_idx)
+                };
+                [
+                    across(tri.index0, tri.index1),
+                    across(tri.index1, tri.index2),
+                    across(tri.index2, tri.index0),
+                ]
+            })
+            .collect()
+    }
+
+    /// everything found wrong with the mesh, empty for a valid one
+    pub(crate) fn validate(&self, points: &[Point]) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
+        let mut edge_count = HashMap::<Edge, usize>::default();
+        let mut seen = HashMap::<[PointIdx; 3], TriIdx>::default();
+        for (idx, tri) in self.triangles.iter().enumerate() {
+            let idx = idx as TriIdx;
+            if tri.area(points) == 0.0 {
+                issues.push(ValidationIssue::DegenerateTriangle(idx));
+            }
+
+            let mut key = [tri.index0, tri.index1, tri.index2];
+            key.sort_unstable();
+            match seen.get(&key) {
+                Some(first) => issues.push(ValidationIssue::DuplicateTriangle(*first, idx)),
+                None => {
+                    seen.insert(key, idx);
+                }
+            }
+
+            for edge in [
+                Edge::new(tri.index0, tri.index1),
+                Edge::new(tri.index1, tri.index2),
+                Edge::new(tri.index2, tri.index0),
+            ] {
+                *edge_count.entry(edge).or_insert(0) += 1;
+            }
+        }
+
+        let mut overused = edge_count
+            .into_iter()
+            .filter(|(_, count)| *count > 2)
+            .map(|(edge, _)| edge)
+            .collect::<Vec<_>>();
+        overused.sort_unstable();
+        issues.extend(overused.into_iter().map(ValidationIssue::EdgeOverused));
+
+        issues.extend(
+            self.tri_edge_mapping
+                .inconsistent_triangles(&self.triangles)
+                .into_iter()
+                .map(ValidationIssue::InconsistentMapping),
+        );
+
+        issues
+    }
+
+    pub(crate) fn points_remaining(&self) -> usize {
+        self.points_to_add.len()
+    }
+
+    /// adds all remaining points
+    pub(crate) fn add_all(&mut self, points: &[Point]) -> Result<(), TriangulatorError> {
+        while self.do_step(points) {}
+
+        if self.flip_limit_exceeded {
+            Err(TriangulatorError::FlipLimitExceeded)
         } else {
-            false
+            self.check_coincident(0)
         }
     }
 
+    /// starts from a given mesh instead of the convex hull, eg a saved or external triangulation.
+    /// points inside it can then be added with insert_point. the mesh should be delaunay for the result to be
+    pub(crate) fn from_existing(
+        triangles: Vec<Triangle>,
+        points: &[Point],
+    ) -> Result<Self, TriangulatorError> {
+        let in_range = |idx: PointIdx| (idx as usize) < points.len();
+        if triangles.is_empty()
+            || !triangles
+                .iter()
+                .all(|t| in_range(t.index0) && in_range(t.index1) && in_range(t.index2))
+        {
+            return Err(TriangulatorError::InvalidMesh);
+        }
+
+        let mut triangulator = Self::with_capacity(triangles.len() / 2);
+        triangulator.triangles = triangles;
+        for i in 0..triangulator.triangles.len() {
+            // a triangle with a repeated vertex can't be mapped, and would fail validation anyway
+            let t = &triangulator.triangles[i];
+            if t.index0 == t.index1 || t.index1 == t.index2 || t.index2 == t.index0 {
+                return Err(TriangulatorError::InvalidMesh);
+            }
+            triangulator
+                .tri_edge_mapping
+                .add_triangle(i as TriIdx, &triangulator.triangles);
+        }
+
+        if !triangulator.validate(points).is_empty() || triangulator.has_inverted_triangles(points)
+        {
+            return Err(TriangulatorError::InvalidMesh);
+        }
+        Ok(triangulator)
+    }
+
+    // the winding of the triangles is arbitrary, so a triangle is inverted, ie has negative signed area
+    // compared to the rest of the mesh, if it is on the same side of an edge as the other triangle sharing it
+    fn has_inverted_triangles(&self, points: &[Point]) -> bool {
+        self.tri_edge_mapping.edges().any(|edge| {
+            let tris = self.tri_edge_mapping.get_triangles(edge);
+            if tris.len() != 2 {
+                return false;
+            }
+            let (a, b) = (
+                &points[edge.index_0 as usize],
+                &points[edge.index_1 as usize],
+            );
+            let side = |tri_idx: TriIdx| {
+                let tri = &self.triangles[tri_idx as usize];
+                let opposite = tri.index0 + tri.index1 + tri.index2 - edge.index_0 - edge.index_1;
+                cross(b, &points[opposite as usize], a)
+            };
+            side(tris[0]) * side(tris[1]) >= 0.0
+        })
+    }
+
+    /// adds a point inside the current mesh, as a step that can be undone
+    pub(crate) fn insert_point(
+        &mut self,
+        point_idx: PointIdx,
+        points: &[Point],
+    ) -> Result<(), TriangulatorError> {
+        let point = points
+            .get(point_idx as usize)
+            .ok_or(TriangulatorError::PointIndexOutOfRange(point_idx as usize))?;
+        if point.x.is_nan() || point.y.is_nan() {
+            return Err(TriangulatorError::NANInInput(point_idx as usize));
+        }
+        if self.containing_triangle(point, points).is_none() {
+            return Err(TriangulatorError::PointOutsideOfHull);
+        }
+
+        self.recording = Some(StepRecord {
+            point_idx,
+            num_triangles: self.triangles.len(),
+            replaced: Vec::new(),
+        });
+        self.flip_limit_exceeded = false;
+        let num_merged = self.merged.len();
+        self.add_point(point_idx, points);
+        self.history.extend(self.recording.take());
+
+        if self.flip_limit_exceeded {
+            return Err(TriangulatorError::FlipLimitExceeded);
+        }
+        self.check_coincident(num_merged)
+    }
+
+    // CoincidentPoints for the first point merged since merged had num_merged entries, if they are rejected
+    fn check_coincident(&self, num_merged: usize) -> Result<(), TriangulatorError> {
+        match self.merged.get(num_merged) {
+            Some(&(skipped, vertex)) if self.options.reject_coincident => {
+                Err(TriangulatorError::CoincidentPoints(
+                    skipped.min(vertex) as usize,
+                    skipped.max(vertex) as usize,
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// adds points[point_idx], which is outside of the triangles, by connecting it to the boundary edges facing it.
+    /// can't be undone, and like flip_edge it clears the undo history, so earlier steps and insert_point can't be
+    /// undone either. gives PointOutsideOfHull if no boundary edge faces the point, ie it is inside
+    pub(crate) fn extend_hull(
+        &mut self,
+        point_idx: PointIdx,
+        points: &[Point],
+    ) -> Result<(), TriangulatorError> {
+        let point = &points[point_idx as usize];
+        if point.x.is_nan() || point.y.is_nan() {
+            return Err(TriangulatorError::NANInInput(point_idx as usize));
+        }
+        let orient = self.orient();
+        let eps = self.geometry_epsilon();
+
+        // directed counter clockwise along the hull, with the mesh on their left
+        let facing = self
+            .tri_edge_mapping
+            .edges()
+            .filter(|edge| self.tri_edge_mapping.is_boundary(edge))
+            .filter_map(|edge| {
+                let tri = &self.triangles[self.tri_edge_mapping.get_triangles(edge)[0] as usize];
+                let opposite = tri.index0 + tri.index1 + tri.index2 - edge.index_0 - edge.index_1;
+                let (a, b) = (
+                    &points[edge.index_0 as usize],
+                    &points[edge.index_1 as usize],
+                );
+                let side = orient_with_tolerance(b, point, a, orient, eps);
+                let inside = orient(b, &points[opposite as usize], a);
+                if side == 0.0 || side.signum() == inside.signum() {
+                    None
+                } else if inside > 0.0 {
+                    Some(DirectedEdge::new(edge.index_0, edge.index_1))
+                } else {
+                    Some(DirectedEdge::new(edge.index_1, edge.index_0))
+                }
+            })
+            .collect::<Vec<_>>();
+        if facing.is_empty() {
+            return Err(TriangulatorError::PointOutsideOfHull);
+        }
+
+        // the point is right of the edge, so reversing it gives a counter clockwise triangle
+        let mut changed_triangles = Vec::with_capacity(facing.len());
+        for edge in facing {
+            self.triangles
+                .push(Triangle::new(edge.to, edge.from, point_idx));
+            let tri_idx = (self.triangles.len() - 1) as TriIdx;
+            self.tri_edge_mapping.add_triangle(tri_idx, &self.triangles);
+            changed_triangles.push(tri_idx);
+        }
+
+        // the recorded steps don't know about the new triangles, so undoing them would break the mesh
+        self.history.clear();
+        self.flip_limit_exceeded = false;
+        self.flip_pairs(changed_triangles, points);
+        if self.flip_limit_exceeded {
+            return Err(TriangulatorError::FlipLimitExceeded);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn flip_limit_exceeded(&self) -> bool {
+        self.flip_limit_exceeded
+    }
+
+    pub(crate) fn do_step(&mut self, points: &[Point]) -> bool {
+        self.step(points).is_some()
+    }
+
+    /// adds the next point, returning its index, or None if all points are added
+    pub(crate) fn step(&mut self, points: &[Point]) -> Option<PointIdx> {
+        let point_idx = self.points_to_add.pop()?;
+
+        self.recording = Some(StepRecord {
+            point_idx,
+            num_triangles: self.triangles.len(),
+            replaced: Vec::new(),
+        });
+        self.add_point(point_idx, points);
+        self.history.extend(self.recording.take());
+
+        Some(point_idx)
+    }
+
+    /// reverts the last step, putting its point back to be added again. false if there is nothing to undo
+    pub(crate) fn undo_step(&mut self) -> bool {
+        let record = match self.history.pop() {
+            Some(record) => record,
+            None => return false,
+        };
+
+        for tri_idx in record.num_triangles..self.triangles.len() {
+            self.tri_edge_mapping.remove_triangle(tri_idx as TriIdx);
+        }
+        self.triangles.truncate(record.num_triangles);
+
+        for (tri_idx, _) in record.replaced.iter() {
+            self.tri_edge_mapping.remove_triangle(*tri_idx);
+        }
+        for (tri_idx, tri) in record.replaced.into_iter() {
+            self.triangles[tri_idx as usize] = tri;
+            self.tri_edge_mapping.add_triangle(tri_idx, &self.triangles);
+        }
+
+        if self.merged.last().map(|(idx, _)| *idx) == Some(record.point_idx) {
+            self.merged.pop();
+        }
+        self.points_to_add.push(record.point_idx);
+        true
+    }
+
+    /// reverts the last insert_point, or step, without adding its point again. false if there is nothing to undo
+    pub(crate) fn undo_insert(&mut self) -> bool {
+        let undone = self.undo_step();
+        if undone {
+            self.points_to_add.pop();
+        }
+        undone
+    }
+
+    // replaces a triangle, remembering the old one if a step is being recorded
+    fn replace_triangle(&mut self, tri_idx: TriIdx, tri: Triangle) {
+        if let Some(record) = self.recording.as_mut() {
+            if (tri_idx as usize) < record.num_triangles
+                && !record.replaced.iter().any(|(idx, _)| *idx == tri_idx)
+            {
+                record
+                    .replaced
+                    .push((tri_idx, self.triangles[tri_idx as usize].clone()));
+            }
+        }
+        self.triangles[tri_idx as usize] = tri;
+    }
+
+    fn containing_triangle(&self, point: &Point, points: &[Point]) -> Option<TriIdx> {
+        let orient = self.orient();
+        let eps = self.geometry_epsilon();
+        self.triangles
+            .iter()
+            .position(|triangle| {
+                let (a, b, c) = (
+                    &points[triangle.index0 as usize],
+                    &points[triangle.index1 as usize],
+                    &points[triangle.index2 as usize],
+                );
+                // most triangles are far away, rule them out with their bounding box before the orientation tests.
+                // a point on an edge is inside the box, within the rounding of the edge, so it's expanded by that
+                let margin =
+                    eps * (a.x.abs() + a.y.abs() + b.x.abs() + b.y.abs() + c.x.abs() + c.y.abs());
+                point.x >= a.x.min(b.x).min(c.x) - margin
+                    && point.x <= a.x.max(b.x).max(c.x) + margin
+                    && point.y >= a.y.min(b.y).min(c.y) - margin
+                    && point.y <= a.y.max(b.y).max(c.y) + margin
+                    && point_in_triangle(point, a, b, c, orient, eps)
+            })
+            .map(|idx| idx as TriIdx)
+    }
+
     fn add_point(&mut self, point_idx: PointIdx, points: &[Point]) {
         // find all triangles whose circumcircle contains the point, starting with the triangle containing the point.
         // work outwards from there, keeping track of the edges of the cavity.
         // delete those triangles, which is guaranteed to create a convex cavity.
         // join the new point with the vertices of the cavity
 
-        let point = &points[point_idx];
+        let point = &points[point_idx as usize];
+        let orient = self.orient();
+
+        let containing = self.containing_triangle(point, points);
+        let containing_triangle_idx = containing.unwrap_or(0);
 
-        let mut containing_triangle_idx = 0;
-        for (idx, triangle) in self.triangles.iter().enumerate() {
-            if point_in_triangle(
+        // on an edge between two triangles, the cavity would need both, so split them directly.
+        // on a boundary edge, the cavity would have a zero area triangle along the edge
+        if containing.is_some() {
+            let containing_triangle = &self.triangles[containing_triangle_idx as usize];
+
+            // a point on top of a vertex would only add zero area triangles
+            for vertex in [
+                containing_triangle.index0,
+                containing_triangle.index1,
+                containing_triangle.index2,
+            ] {
+                if coincident(&points[vertex as usize], point, self.geometry_epsilon()) {
+                    self.merged.push((point_idx, vertex));
+                    return;
+                }
+            }
+
+            if let Some(edge) = point_on_edge(
                 point,
-                &points[triangle.index0],
-                &points[triangle.index1],
-                &points[triangle.index2],
+                containing_triangle,
+                points,
+                orient,
+                self.geometry_epsilon(),
             ) {
-                containing_triangle_idx = idx;
-                break;
+                self.split_edge(point_idx, &edge, points);
+                return;
             }
         }
 
         let mut triangles_to_check = vec![containing_triangle_idx];
         let mut triangles_to_remove = Vec::new();
-        let mut cavity_edges = HashSet::<Edge>::new();
+        let mut cavity_edges = HashSet::<Edge>::default();
+
+        // the cavity of a mesh that isn't delaunay may not be star shaped around the point, so only the containing triangle is used
+        let whole_cavity = self.options.flip_criterion == FlipCriterion::Delaunay;
 
         // build cavity edge list and flag triangles for removal
         while let Some(triangle_to_check) = triangles_to_check.pop() {
-            let circle = Circle::from_triangle(&self.triangles[triangle_to_check], points);
-            if circle.contains(point) {
+            if (whole_cavity || triangle_to_check == containing_triangle_idx)
+                && in_circle(
+                    &self.triangles[triangle_to_check as usize],
+                    point_idx,
+                    points,
+                    &self.weights,
+                    self.exact,
+                )
+            {
                 //flag triangle for removal
                 triangles_to_remove.push(triangle_to_check);
 
@@ -147,7 +1231,7 @@ impl DelaunayIncremental {
         for tri_idx in triangles_to_remove.iter() {
             let cavity_edge = cavity_edges.pop().unwrap();
             let new_tri = Triangle::new(point_idx, cavity_edge.index_0, cavity_edge.index_1);
-            self.triangles[*tri_idx] = new_tri;
+            self.replace_triangle(*tri_idx, new_tri);
             self.tri_edge_mapping
                 .add_triangle(*tri_idx, &self.triangles);
         }
@@ -156,7 +1240,7 @@ impl DelaunayIncremental {
         while let Some(cavity_edge) = cavity_edges.pop() {
             let new_tri = Triangle::new(point_idx, cavity_edge.index_0, cavity_edge.index_1);
             self.triangles.push(new_tri);
-            let tri_idx = self.triangles.len() - 1;
+            let tri_idx = (self.triangles.len() - 1) as TriIdx;
             self.tri_edge_mapping.add_triangle(tri_idx, &self.triangles);
             changed_triangles.push(tri_idx);
         }
@@ -165,20 +1249,76 @@ impl DelaunayIncremental {
         self.flip_pairs(changed_triangles, points);
     }
 
+    // replaces the two triangles of edge with four, or the one of a boundary edge with two, joined at point_idx which is on the edge
+    fn split_edge(&mut self, point_idx: PointIdx, edge: &Edge, points: &[Point]) {
+        let tris = self.tri_edge_mapping.get_triangles(edge);
+        let mut changed_triangles = Vec::with_capacity(4);
+
+        for tri_idx in tris {
+            let tri = &self.triangles[tri_idx as usize];
+            let opposite = tri.index0 + tri.index1 + tri.index2 - edge.index_0 - edge.index_1;
+
+            self.tri_edge_mapping.remove_triangle(tri_idx);
+            self.replace_triangle(tri_idx, Triangle::new(opposite, edge.index_0, point_idx));
+            self.tri_edge_mapping.add_triangle(tri_idx, &self.triangles);
+
+            self.triangles
+                .push(Triangle::new(opposite, point_idx, edge.index_1));
+            let new_idx = (self.triangles.len() - 1) as TriIdx;
+            self.tri_edge_mapping.add_triangle(new_idx, &self.triangles);
+
+            changed_triangles.push(tri_idx);
+            changed_triangles.push(new_idx);
+        }
+
+        self.flip_pairs(changed_triangles, points);
+    }
+
     fn flip_pairs(&mut self, mut check_stack: Vec<TriIdx>, points: &[Point]) {
-        let mut already_checked = HashSet::new();
+        // edges flipped away are never flipped back, which guards against cycling on cocircular points
+        let mut removed_edges = HashSet::default();
+        // so it terminates, but in theory only after a quadratic number of flips. in practice restoring delaunay
+        // takes a few per triangle, so more means a bug, and it gives up rather than hang
+        let max_flips = self
+            .options
+            .max_flips_per_triangle
+            .saturating_mul(self.triangles.len());
+        let mut flips = 0;
 
         while let Some(tri) = check_stack.pop() {
-            let neighbours = self.tri_edge_mapping.neighbouring_triangles(tri);
-            for neighbour in neighbours.into_iter() {
-                let pair = OrderedPair::new(tri, neighbour);
+            // at most three, kept on the stack since this is the inner loop of the triangulation
+            let mut neighbours = [None; 3];
+            let mut num_neighbours = 0;
+            self.tri_edge_mapping.for_each_neighbour(tri, |neighbour| {
+                if num_neighbours < neighbours.len() {
+                    neighbours[num_neighbours] = Some(neighbour);
+                    num_neighbours += 1;
+                }
+            });
+            for neighbour in neighbours.iter().flatten().copied() {
+                let ((non_common_a, non_common_b), (common_0, common_1)) = commonality(
+                    &self.triangles[tri as usize],
+                    &self.triangles[neighbour as usize],
+                );
 
-                if !already_checked.contains(&pair)
-                    && should_flip(&self.triangles[tri], &self.triangles[neighbour], points)
+                if !removed_edges.contains(&Edge::new(non_common_a, non_common_b))
+                    && should_flip(
+                        &self.triangles[tri as usize],
+                        &self.triangles[neighbour as usize],
+                        points,
+                        &self.weights,
+                        self.exact,
+                        self.options.flip_criterion,
+                    )
                 {
+                    if flips >= max_flips {
+                        self.flip_limit_exceeded = true;
+                        return;
+                    }
+                    flips += 1;
                     self.flip(tri, neighbour);
 
-                    already_checked.insert(pair);
+                    removed_edges.insert(Edge::new(common_0, common_1));
 
                     // the new triangles should be checked
                     check_stack.push(tri);
@@ -190,9 +1330,303 @@ impl DelaunayIncremental {
         }
     }
 
+    pub(crate) fn flip_edge(&mut self, edge: &Edge, points: &[Point]) -> Result<(), FlipError> {
+        if !self.tri_edge_mapping.contains_edge(edge) {
+            return Err(FlipError::UnknownEdge);
+        }
+
+        let tris = self.tri_edge_mapping.get_triangles(edge);
+        if tris.len() != 2 {
+            return Err(FlipError::BoundaryEdge);
+        }
+
+        if !is_convex_quad(
+            &self.triangles[tris[0] as usize],
+            &self.triangles[tris[1] as usize],
+            points,
+            self.orient(),
+        ) {
+            return Err(FlipError::NonConvexQuad);
+        }
+
+        self.flip(tris[0], tris[1]);
+        self.history.clear();
+        Ok(())
+    }
+
+    /// the neighbours of an interior vertex as a counter clockwise polygon, None for boundary and unused vertices
+    pub(crate) fn ordered_ring(&self, vertex: PointIdx, points: &[Point]) -> Option<Vec<PointIdx>> {
+        let mut tris = self.tri_edge_mapping.get_vertex_triangles(vertex);
+        if tris.len() < 3 {
+            return None;
+        }
+        tris.sort_unstable();
+
+        // the edge opposite of vertex in each triangle, counter clockwise as seen from vertex
+        let mut next = HashMap::default();
+        for tri_idx in &tris {
+            let tri = &self.triangles[*tri_idx as usize];
+            let (a, b) = if tri.index0 == vertex {
+                (tri.index1, tri.index2)
+            } else if tri.index1 == vertex {
+                (tri.index2, tri.index0)
+            } else {
+                (tri.index0, tri.index1)
+            };
+            if cross(
+                &points[a as usize],
+                &points[b as usize],
+                &points[vertex as usize],
+            ) < 0.0
+            {
+                next.insert(b, a);
+            } else {
+                next.insert(a, b);
+            }
+        }
+
+        // a boundary vertex has a gap in its ring
+        let start = *next.keys().min()?;
+        let mut ring = vec![start];
+        let mut current = start;
+        loop {
+            current = *next.get(&current)?;
+            if current == start {
+                break;
+            }
+            if ring.len() == tris.len() {
+                return None;
+            }
+            ring.push(current);
+        }
+        if ring.len() == tris.len() {
+            Some(ring)
+        } else {
+            None
+        }
+    }
+
+    /// removes an interior vertex, filling its hole with delaunay triangles.
+    /// false, with nothing changed, for boundary and unused vertices
+    pub(crate) fn remove_point(&mut self, vertex: PointIdx, points: &[Point]) -> bool {
+        let filled = match self
+            .ordered_ring(vertex, points)
+            .and_then(|ring| triangulate_star_polygon(&ring, points))
+        {
+            Some(filled) => filled,
+            None => return false,
+        };
+        let mut removed = self.tri_edge_mapping.get_vertex_triangles(vertex);
+        removed.sort_unstable();
+
+        // the hole needs two triangles less, reuse the first slots and drop the last two
+        for tri_idx in removed.iter() {
+            self.tri_edge_mapping.remove_triangle(*tri_idx);
+        }
+        let mut changed_triangles = Vec::with_capacity(filled.len());
+        for (tri_idx, tri) in removed.iter().copied().zip(filled) {
+            self.triangles[tri_idx as usize] = tri;
+            self.tri_edge_mapping.add_triangle(tri_idx, &self.triangles);
+            changed_triangles.push(tri_idx);
+        }
+        for tri_idx in removed[changed_triangles.len()..].iter().rev() {
+            self.swap_remove_triangle(*tri_idx);
+        }
+        self.history.clear();
+
+        self.flip_pairs(changed_triangles, points);
+        true
+    }
+
+    /// moves vertex to new_pos, flipping around it if its triangles keep their orientation,
+    /// otherwise removing and inserting it again. only for interior vertices, as moving one on the hull
+    /// could make it concave. if the triangles would flip and new_pos is outside of them, gives PointOutsideOfHull
+    /// without changing anything, points included
+    pub(crate) fn move_vertex(
+        &mut self,
+        points: &mut [Point],
+        vertex: PointIdx,
+        new_pos: Point,
+    ) -> Result<(), TriangulatorError> {
+        if new_pos.x.is_nan() || new_pos.y.is_nan() {
+            return Err(TriangulatorError::NANInInput(vertex as usize));
+        }
+        let incident = self.tri_edge_mapping.get_vertex_triangles(vertex);
+        if incident.is_empty() {
+            points[vertex as usize] = new_pos;
+            return Ok(());
+        }
+
+        if self.ordered_ring(vertex, points).is_none() {
+            return Err(TriangulatorError::PointOutsideOfHull);
+        }
+
+        let position = |idx: PointIdx| {
+            if idx == vertex {
+                new_pos
+            } else {
+                points[idx as usize]
+            }
+        };
+        let keeps_orientation = incident.iter().all(|tri_idx| {
+            let tri = &self.triangles[*tri_idx as usize];
+            let before = cross(
+                &points[tri.index1 as usize],
+                &points[tri.index2 as usize],
+                &points[tri.index0 as usize],
+            );
+            let after = cross(
+                &position(tri.index1),
+                &position(tri.index2),
+                &position(tri.index0),
+            );
+            before * after > 0.0
+        });
+
+        self.flip_limit_exceeded = false;
+        if keeps_orientation {
+            // only the triangles of vertex changed, so only their edges can have become non delaunay
+            points[vertex as usize] = new_pos;
+            self.history.clear();
+            self.flip_pairs(incident, points);
+        } else {
+            if self.containing_triangle(&new_pos, points).is_none() {
+                return Err(TriangulatorError::PointOutsideOfHull);
+            }
+            if !self.remove_point(vertex, points) {
+                return Err(TriangulatorError::InvalidMesh);
+            }
+            points[vertex as usize] = new_pos;
+            self.add_point(vertex, points);
+        }
+
+        if self.flip_limit_exceeded {
+            return Err(TriangulatorError::FlipLimitExceeded);
+        }
+        Ok(())
+    }
+
+    // removes a triangle that is already out of the mapping, by moving the last one into its place
+    fn swap_remove_triangle(&mut self, tri_idx: TriIdx) {
+        let last = (self.triangles.len() - 1) as TriIdx;
+        if tri_idx != last {
+            self.tri_edge_mapping.remove_triangle(last);
+        }
+        self.triangles.swap_remove(tri_idx as usize);
+        if tri_idx != last {
+            self.tri_edge_mapping.add_triangle(tri_idx, &self.triangles);
+        }
+    }
+
+    /// gives each point the index new_index returns for it. points it returns None for must be unused
+    pub(crate) fn renumber_points(&mut self, new_index: impl Fn(PointIdx) -> Option<PointIdx>) {
+        let renumbered = |idx: PointIdx| new_index(idx).expect("renumbered point is used");
+        for tri in self.triangles.iter_mut() {
+            *tri = Triangle::new(
+                renumbered(tri.index0),
+                renumbered(tri.index1),
+                renumbered(tri.index2),
+            );
+        }
+        self.merged = self
+            .merged
+            .iter()
+            .filter_map(|(skipped, vertex)| Some((new_index(*skipped)?, new_index(*vertex)?)))
+            .collect();
+        for indices in [
+            &mut self.points_to_add,
+            &mut self.hull,
+            &mut self.hull_interior,
+        ] {
+            *indices = indices.iter().filter_map(|idx| new_index(*idx)).collect();
+        }
+        self.retain_triangles(|_| true);
+    }
+
+    /// merges the end points of edge into its midpoint, which is stored at edge.index_0.
+    /// edge.index_1 is left unused by the triangulation
+    pub(crate) fn collapse_edge(
+        &mut self,
+        edge: &Edge,
+        points: &mut [Point],
+    ) -> Result<(), CollapseError> {
+        if !self.tri_edge_mapping.contains_edge(edge) {
+            return Err(CollapseError::UnknownEdge);
+        }
+        let (keep, remove) = (edge.index_0, edge.index_1);
+        let incident = self.tri_edge_mapping.get_triangles(edge);
+
+        // the end points may only share the neighbours opposite of the edge, or the mesh would fold onto itself
+        let opposite = incident
+            .iter()
+            .map(|tri_idx| {
+                let tri = &self.triangles[*tri_idx as usize];
+                tri.index0 + tri.index1 + tri.index2 - keep - remove
+            })
+            .collect::<Vec<_>>();
+        let ring_remove = self.one_ring(remove);
+        let shares_other = self
+            .one_ring(keep)
+            .iter()
+            .any(|idx| ring_remove.contains(idx) && !opposite.contains(idx));
+        if shares_other {
+            return Err(CollapseError::NonManifold);
+        }
+
+        let midpoint = (points[keep as usize] + &points[remove as usize]) * 0.5;
+        let mut affected = self.triangles_around(keep);
+        affected.extend(self.triangles_around(remove));
+        affected.retain(|tri_idx| !incident.contains(tri_idx));
+
+        let mut collapsed = Vec::with_capacity(affected.len());
+        for tri_idx in affected {
+            let tri = &self.triangles[tri_idx as usize];
+            let replace = |idx: PointIdx| if idx == remove { keep } else { idx };
+            let new_tri = Triangle::new(
+                replace(tri.index0),
+                replace(tri.index1),
+                replace(tri.index2),
+            );
+
+            let position = |idx: PointIdx| {
+                if idx == keep {
+                    midpoint
+                } else {
+                    points[idx as usize]
+                }
+            };
+            let before = cross(
+                &points[tri.index1 as usize],
+                &points[tri.index2 as usize],
+                &points[tri.index0 as usize],
+            );
+            let after = cross(
+                &position(new_tri.index1),
+                &position(new_tri.index2),
+                &position(new_tri.index0),
+            );
+            if before * after <= 0.0 {
+                return Err(CollapseError::InvertsTriangle);
+            }
+            collapsed.push((tri_idx, new_tri));
+        }
+
+        points[keep as usize] = midpoint;
+        for (tri_idx, new_tri) in collapsed {
+            self.triangles[tri_idx as usize] = new_tri;
+        }
+        let mut tri_idx = 0;
+        self.retain_triangles(|_| {
+            tri_idx += 1;
+            !incident.contains(&(tri_idx - 1))
+        });
+
+        Ok(())
+    }
+
     fn flip(&mut self, a: TriIdx, b: TriIdx) {
-        let tri_a = &self.triangles[a];
-        let tri_b = &self.triangles[b];
+        let tri_a = &self.triangles[a as usize];
+        let tri_b = &self.triangles[b as usize];
 
         let ((non_common_a, non_common_b), (common_0, common_1)) = commonality(tri_a, tri_b);
 
@@ -202,21 +1636,89 @@ impl DelaunayIncremental {
         let new_tri_a = Triangle::new(non_common_a, non_common_b, common_0);
         let new_tri_b = Triangle::new(non_common_a, non_common_b, common_1);
 
-        self.triangles[a] = new_tri_a;
-        self.triangles[b] = new_tri_b;
+        self.replace_triangle(a, new_tri_a);
+        self.replace_triangle(b, new_tri_b);
 
         self.tri_edge_mapping.add_triangle(a, &self.triangles);
         self.tri_edge_mapping.add_triangle(b, &self.triangles);
     }
 }
 
-fn should_flip(a: &Triangle, b: &Triangle, points: &[Point]) -> bool {
-    let circle_a = Circle::from_triangle(a, points);
-    let circle_b = Circle::from_triangle(a, points);
-    debug_assert!(circle_a.radius_sqr.is_finite() && circle_b.radius_sqr.is_finite());
+fn should_flip(
+    a: &Triangle,
+    b: &Triangle,
+    points: &[Point],
+    weights: &[f32],
+    exact: bool,
+    criterion: FlipCriterion,
+) -> bool {
+    let ((point_a, point_b), (common_0, common_1)) = commonality(a, b);
+    let orient = if exact { exact::orient } else { cross };
+    let improves = match criterion {
+        FlipCriterion::Delaunay => in_circle(a, point_b, points, weights, exact),
+        FlipCriterion::MinMaxAngle => {
+            let flipped = [
+                Triangle::new(point_a, point_b, common_0),
+                Triangle::new(point_a, point_b, common_1),
+            ];
+            let max_angle = |tris: &[Triangle]| {
+                tris.iter()
+                    .map(|tri| tri.max_angle(points))
+                    .fold(0.0, f32::max)
+            };
+            // a margin, so that rounding can't make the flip look like an improvement both ways
+            max_angle(&flipped) < max_angle(&[a.clone(), b.clone()]) - 1e-6
+        }
+    };
+    improves && is_convex_quad(a, b, points, orient)
+}
+
+// circumcircle test, or power circle test if weights are given.
+// a degenerate (collinear) triangle has an infinite circle, containing everything
+fn in_circle(
+    tri: &Triangle,
+    point_idx: PointIdx,
+    points: &[Point],
+    weights: &[f32],
+    exact: bool,
+) -> bool {
+    let point = &points[point_idx as usize];
+    if exact {
+        exact::in_circle(
+            &points[tri.index0 as usize],
+            &points[tri.index1 as usize],
+            &points[tri.index2 as usize],
+            point,
+        )
+    } else if weights.is_empty() {
+        let circle = Circle::from_triangle(tri, points);
+        !circle.radius_sqr.is_finite() || circle.contains(point)
+    } else {
+        let circle = Circle::from_triangle_weighted(tri, points, weights);
+        !circle.radius_sqr.is_finite()
+            || circle.contains_weighted(point, weights[point_idx as usize])
+    }
+}
 
-    let ((_point_a, point_b), (_, _)) = commonality(a, b);
-    circle_a.contains(&points[point_b])
+// two triangles sharing an edge form a convex quad if the other diagonal crosses the shared edge
+fn is_convex_quad(
+    a: &Triangle,
+    b: &Triangle,
+    points: &[Point],
+    orient: fn(&Point, &Point, &Point) -> f32,
+) -> bool {
+    let ((non_common_a, non_common_b), (common_0, common_1)) = commonality(a, b);
+    let side_0 = orient(
+        &points[non_common_b as usize],
+        &points[common_0 as usize],
+        &points[non_common_a as usize],
+    );
+    let side_1 = orient(
+        &points[non_common_b as usize],
+        &points[common_1 as usize],
+        &points[non_common_a as usize],
+    );
+    side_0 * side_1 < 0.0
 }
 
 fn commonality(a: &Triangle, b: &Triangle) -> ((PointIdx, PointIdx), (PointIdx, PointIdx)) {
@@ -251,29 +1753,610 @@ fn commonality(a: &Triangle, b: &Triangle) -> ((PointIdx, PointIdx), (PointIdx,
     )
 }
 
-fn generate_triangles_from_hull(hull: &[PointIdx]) -> Vec<Triangle> {
-    let mut triangles = Vec::new();
-    triangles.reserve(hull.len() / 3);
+// joins two counter clockwise polygons sharing edge, dropping it. None if edge isn't a side of both
+fn merge_along_edge(a: &[PointIdx], b: &[PointIdx], edge: &Edge) -> Option<Vec<PointIdx>> {
+    // a has the edge as u -> v, b as v -> u
+    let side_start = |polygon: &[PointIdx], from: PointIdx, to: PointIdx| {
+        (0..polygon.len()).find(|i| polygon[*i] == from && polygon[(i + 1) % polygon.len()] == to)
+    };
+    let (u, v, start_a) = match side_start(a, edge.index_0, edge.index_1) {
+        Some(i) => (edge.index_0, edge.index_1, i),
+        None => (
+            edge.index_1,
+            edge.index_0,
+            side_start(a, edge.index_1, edge.index_0)?,
+        ),
+    };
+    let start_b = side_start(b, v, u)?;
+
+    // a from v around to u, then b from after u to before v
+    let mut merged = Vec::with_capacity(a.len() + b.len() - 2);
+    merged.extend((1..=a.len()).map(|i| a[(start_a + i) % a.len()]));
+    merged.extend((2..b.len()).map(|i| b[(start_b + i) % b.len()]));
+    Some(merged)
+}
+
+// no clockwise turns, collinear corners are allowed
+fn is_convex(polygon: &[PointIdx], points: &[Point]) -> bool {
+    let n = polygon.len();
+    (0..n).all(|i| {
+        let prev = &points[polygon[(i + n - 1) % n] as usize];
+        let curr = &points[polygon[i] as usize];
+        let next = &points[polygon[(i + 1) % n] as usize];
+        cross(curr, next, prev) >= 0.0
+    })
+}
+
+/// ear clipping of a counter clockwise polygon, such as the hole left by removing a vertex.
+/// None if no ear is found, which rounding can cause for nearly collinear vertices
+pub(crate) fn triangulate_star_polygon(
+    polygon: &[PointIdx],
+    points: &[Point],
+) -> Option<Vec<Triangle>> {
+    let p = |idx: PointIdx| &points[idx as usize];
+    let mut polygon = polygon.to_vec();
+    let mut triangles = Vec::with_capacity(polygon.len().saturating_sub(2));
 
-    for i in 2..hull.len() {
-        triangles.push(Triangle::new(hull[0], hull[i - 1], hull[i]));
+    while polygon.len() >= 3 {
+        let n = polygon.len();
+        let ear = (0..n).find(|&i| {
+            let (a, b, c) = (polygon[(i + n - 1) % n], polygon[i], polygon[(i + 1) % n]);
+            cross(p(c), p(a), p(b)) > 0.0
+                && polygon.iter().all(|&other| {
+                    other == a
+                        || other == b
+                        || other == c
+                        || cross(p(b), p(other), p(a)) < 0.0
+                        || cross(p(c), p(other), p(b)) < 0.0
+                        || cross(p(a), p(other), p(c)) < 0.0
+                })
+        })?;
+        triangles.push(Triangle::new(
+            polygon[(ear + n - 1) % n],
+            polygon[ear],
+            polygon[(ear + 1) % n],
+        ));
+        polygon.remove(ear);
     }
 
-    triangles
+    Some(triangles)
 }
 
-fn point_in_triangle(point: &Point, a: &Point, b: &Point, c: &Point) -> bool {
-    if cross(a, b, c).abs() < f32::EPSILON {
+fn point_in_triangle(
+    point: &Point,
+    a: &Point,
+    b: &Point,
+    c: &Point,
+    orient: fn(&Point, &Point, &Point) -> f32,
+    eps: f32,
+) -> bool {
+    // without a tolerance, small triangles count as degenerate, as always
+    let degenerate = if eps > 0.0 {
+        orient_with_tolerance(a, b, c, orient, eps) == 0.0
+    } else {
+        orient(a, b, c).abs() < f32::EPSILON
+    };
+    if degenerate {
         return false;
     }
 
-    same_side_of_line(point, a, b, c)
-        && same_side_of_line(point, b, a, c)
-        && same_side_of_line(point, c, a, b)
+    same_side_of_line(point, a, b, c, orient, eps)
+        && same_side_of_line(point, b, a, c, orient, eps)
+        && same_side_of_line(point, c, a, b, orient, eps)
+}
+
+fn same_side_of_line(
+    p0: &Point,
+    p1: &Point,
+    linestart: &Point,
+    lineend: &Point,
+    orient: fn(&Point, &Point, &Point) -> f32,
+    eps: f32,
+) -> bool {
+    let cp1 = orient_with_tolerance(lineend, p0, linestart, orient, eps);
+    let cp2 = orient(lineend, p1, linestart);
+    // on the line counts as on the same side
+    cp1 == 0.0 || cp1.signum() == cp2.signum()
+}
+
+// at the same position, within eps relative to the magnitude of the coordinates. exactly equal for eps 0
+fn coincident(a: &Point, b: &Point, eps: f32) -> bool {
+    let scale = a.x.abs().max(a.y.abs()).max(b.x.abs()).max(b.y.abs());
+    (a.x - b.x).abs() <= eps * scale && (a.y - b.y).abs() <= eps * scale
+}
+
+// the edge of tri that point is on, if it is on exactly one of them. on two means it is on a vertex
+fn point_on_edge(
+    point: &Point,
+    tri: &Triangle,
+    points: &[Point],
+    orient: fn(&Point, &Point, &Point) -> f32,
+    eps: f32,
+) -> Option<Edge> {
+    let mut on_edge = None;
+    for (start, end) in [
+        (tri.index0, tri.index1),
+        (tri.index1, tri.index2),
+        (tri.index2, tri.index0),
+    ] {
+        let (start_point, end_point) = (&points[start as usize], &points[end as usize]);
+        if orient_with_tolerance(end_point, point, start_point, orient, eps) == 0.0 {
+            if on_edge.is_some() {
+                return None;
+            }
+            on_edge = Some(Edge::new(start, end));
+        }
+    }
+    on_edge
 }
 
-fn same_side_of_line(p0: &Point, p1: &Point, linestart: &Point, lineend: &Point) -> bool {
-    let cp1 = cross(lineend, p0, linestart);
-    let cp2 = cross(lineend, p1, linestart);
-    cp1.signum() == cp2.signum()
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn near_edge_classified_same_at_any_scale() {
+        let eps = f32::EPSILON;
+        let tri = Triangle::new(0, 1, 2);
+
+        for scale in [1e-4, 1.0, 1e4] {
+            let points = [
+                Point::new(0.0, 0.0),
+                Point::new(scale, 0.0),
+                Point::new(0.0, scale),
+            ];
+            let [a, b, c] = points;
+            let on_edge = Point::new(0.5 * scale, 1e-9 * scale);
+            let just_inside = Point::new(0.5 * scale, 1e-4 * scale);
+            let just_outside = Point::new(0.5 * scale, -1e-4 * scale);
+
+            assert!(point_in_triangle(&on_edge, &a, &b, &c, cross, eps));
+            assert_eq!(
+                point_on_edge(&on_edge, &tri, &points, cross, eps),
+                Some(Edge::new(0, 1))
+            );
+            assert!(point_in_triangle(&just_inside, &a, &b, &c, cross, eps));
+            assert_eq!(point_on_edge(&just_inside, &tri, &points, cross, eps), None);
+            assert!(!point_in_triangle(&just_outside, &a, &b, &c, cross, eps));
+
+            // a larger tolerance takes in the points near the edge on both sides
+            assert!(point_in_triangle(&just_outside, &a, &b, &c, cross, 1e-3));
+        }
+    }
+
+    fn triangulate_with_criterion(
+        points: &[Point],
+        criterion: FlipCriterion,
+    ) -> DelaunayIncremental {
+        let mut triangulator = DelaunayIncremental::new();
+        triangulator.options.flip_criterion = criterion;
+        triangulator.initial_triangulation(points).unwrap();
+        triangulator.add_all(points).unwrap();
+        triangulator
+    }
+
+    #[test]
+    fn bounding_box_reject_finds_same_triangle() {
+        let mut rng = StdRng::seed_from_u64(15);
+        // on a grid, so that many queries are on edges and vertices
+        let points = (0..200)
+            .map(|_| Point::new(rng.gen_range(0..20) as f32, rng.gen_range(0..20) as f32))
+            .collect::<Vec<_>>();
+        let triangulator = triangulate_with_criterion(&points, FlipCriterion::Delaunay);
+        let eps = triangulator.geometry_epsilon();
+
+        for _ in 0..500 {
+            let query = Point::new(
+                rng.gen_range(0..40) as f32 * 0.5,
+                rng.gen_range(0..40) as f32 * 0.5,
+            );
+            let brute = triangulator.triangles.iter().position(|tri| {
+                point_in_triangle(
+                    &query,
+                    &points[tri.index0 as usize],
+                    &points[tri.index1 as usize],
+                    &points[tri.index2 as usize],
+                    cross,
+                    eps,
+                )
+            });
+            assert_eq!(
+                triangulator.containing_triangle(&query, &points),
+                brute.map(|idx| idx as TriIdx)
+            );
+        }
+    }
+
+    #[test]
+    fn flip_criteria() {
+        let mut rng = StdRng::seed_from_u64(8);
+        let points = (0..80)
+            .map(|_| Point::new(rng.gen_range(0.0..10.0), rng.gen_range(0.0..3.0)))
+            .collect::<Vec<_>>();
+
+        let delaunay = triangulate_with_criterion(&points, FlipCriterion::Delaunay);
+        assert_eq!(delaunay.triangles, triangulate(&points).unwrap());
+
+        let min_max = triangulate_with_criterion(&points, FlipCriterion::MinMaxAngle);
+        assert!(min_max.validate(&points).is_empty());
+        let area =
+            |t: &DelaunayIncremental| t.triangles.iter().map(|tri| tri.area(&points)).sum::<f32>();
+        assert!((area(&min_max) - area(&delaunay)).abs() < 1e-3);
+        assert_ne!(min_max.triangles, delaunay.triangles);
+
+        // no flip left that would lower the largest angle
+        for edge in min_max.tri_edge_mapping.edges() {
+            let tris = min_max.tri_edge_mapping.get_triangles(edge);
+            if tris.len() == 2 {
+                assert!(!should_flip(
+                    &min_max.triangles[tris[0] as usize],
+                    &min_max.triangles[tris[1] as usize],
+                    &points,
+                    &[],
+                    false,
+                    FlipCriterion::MinMaxAngle,
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn convex_polygon_same_as_point_set() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let mut angles = (0..12)
+            .map(|_| rng.gen_range(0.0..std::f32::consts::TAU))
+            .collect::<Vec<_>>();
+        angles.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        // an ellipse, so most triangulations of it aren't delaunay
+        let points = angles
+            .iter()
+            .map(|a| Point::new(4.0 * a.cos(), a.sin()))
+            .collect::<Vec<_>>();
+
+        let triangles = triangulate_convex_polygon(&points).unwrap();
+        let expected = triangulate(&points).unwrap();
+
+        assert_eq!(triangles.len(), points.len() - 2);
+        assert_eq!(triangles.len(), expected.len());
+        for tri in triangles.iter() {
+            assert!(expected.iter().any(|e| e.equivalent(tri)));
+        }
+    }
+
+    #[test]
+    fn flat_hull_within_flip_limit() {
+        // flipping a fan of a flat hull to delaunay would take a quadratic number of flips
+        let mut rng = StdRng::seed_from_u64(9);
+        let points = (0..3000)
+            .map(|_| {
+                let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+                Point::new(100.0 * angle.cos(), angle.sin())
+            })
+            .collect::<Vec<_>>();
+
+        let triangulator = triangulate_with_criterion(&points, FlipCriterion::Delaunay);
+        assert!(!triangulator.flip_limit_exceeded);
+    }
+
+    #[test]
+    fn point_on_boundary_edge_splits_it() {
+        // rounding puts the last point just inside of the hull, but exactly on the edge 0-1 in f32.
+        // its cavity would keep the edge, with a sliver triangle between the edge and the point
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(3.0, 1.3),
+            Point::new(1.0, 30.0),
+            Point::new(2.0, 0.8666667),
+        ];
+        assert_eq!(crate::convex_hull::interior_points(&points).unwrap(), [3]);
+
+        let triangulator = triangulate_with_criterion(&points, FlipCriterion::Delaunay);
+        assert_eq!(triangulator.triangles.len(), 2);
+        assert!(!triangulator
+            .tri_edge_mapping
+            .contains_edge(&Edge::new(0, 1)));
+        assert!(triangulator.is_boundary_edge(&Edge::new(0, 3)));
+        assert!(triangulator.is_boundary_edge(&Edge::new(3, 1)));
+    }
+
+    #[test]
+    fn near_coincident_merged_at_any_scale() {
+        for scale in [1e-4, 1.0, 1e4] {
+            let point = Point::new(0.4 * scale, 0.55 * scale);
+            let points = [
+                Point::new(0.0, 0.0),
+                Point::new(scale, 0.0),
+                Point::new(scale, scale),
+                Point::new(0.0, scale),
+                point,
+                Point::new(point.x * (1.0 + 2e-7), point.y),
+            ];
+            assert_ne!(points[4].x, points[5].x);
+
+            let triangulator = triangulate_with_criterion(&points, FlipCriterion::Delaunay);
+            assert!(triangulator.merged.is_empty());
+
+            let mut triangulator = DelaunayIncremental::new();
+            triangulator.options.geometry_epsilon = 4.0 * f32::EPSILON;
+            triangulator.initial_triangulation(&points).unwrap();
+            triangulator.add_all(&points).unwrap();
+            assert_eq!(triangulator.merged.len(), 1);
+            assert_eq!(triangulator.triangles.len(), 4);
+        }
+    }
+
+    #[test]
+    fn default_tolerance_keeps_exact_tests() {
+        // inside of the hull, off the edge 0-1 by less than f32::EPSILON relative to its length
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(3.0, 0.0),
+            Point::new(1.0, 30.0),
+            Point::new(1.5, 1e-7),
+        ];
+        assert_eq!(crate::convex_hull::interior_points(&points).unwrap(), [3]);
+
+        let triangulator = triangulate_with_criterion(&points, FlipCriterion::Delaunay);
+        assert_eq!(triangulator.triangles.len(), 3);
+        assert!(triangulator.is_boundary_edge(&Edge::new(0, 1)));
+
+        let mut triangulator = DelaunayIncremental::new();
+        triangulator.options.geometry_epsilon = f32::EPSILON;
+        triangulator.initial_triangulation(&points).unwrap();
+        triangulator.add_all(&points).unwrap();
+        assert_eq!(triangulator.triangles.len(), 2);
+
+        // twice the area is below f32::EPSILON, so it's degenerate at the default, though not flat
+        let eps = TriangulatorOptions::default().geometry_epsilon;
+        let [a, b, c] = [
+            Point::new(0.0, 0.0),
+            Point::new(1e-4, 0.0),
+            Point::new(0.0, 1e-4),
+        ];
+        let centroid = Point::new(1e-4 / 3.0, 1e-4 / 3.0);
+        assert!(!point_in_triangle(&centroid, &a, &b, &c, cross, eps));
+        assert!(point_in_triangle(
+            &centroid,
+            &a,
+            &b,
+            &c,
+            cross,
+            f32::EPSILON
+        ));
+    }
+
+    #[test]
+    fn extended_hull_triangles_are_counter_clockwise() {
+        // far out from each side, so that the new triangle isn't flipped
+        for outside in [
+            Point::new(0.5, -2.0),
+            Point::new(-2.0, 0.5),
+            Point::new(3.0, 3.0),
+        ] {
+            let points = [
+                Point::new(0.0, 0.0),
+                Point::new(1.0, 0.0),
+                Point::new(0.0, 1.0),
+                outside,
+            ];
+            let mut triangulator = DelaunayIncremental::new();
+            triangulator.initial_triangulation(&points[..3]).unwrap();
+            triangulator.extend_hull(3, &points).unwrap();
+
+            assert_eq!(triangulator.triangles.len(), 2);
+            let new_tri = &triangulator.triangles[1];
+            assert_eq!(new_tri.index2, 3);
+            let winding = cross(
+                &points[new_tri.index1 as usize],
+                &points[new_tri.index2 as usize],
+                &points[new_tri.index0 as usize],
+            );
+            assert!(winding > 0.0);
+        }
+    }
+
+    #[test]
+    fn default_order_pops_from_hull() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let points = (0..30)
+            .map(|_| Point::new(rng.gen_range(0.0..10.0), rng.gen_range(0.0..10.0)))
+            .collect::<Vec<_>>();
+        let (_, interior) = convex_hull(&points, cross, 0.0).unwrap();
+
+        let mut triangulator = DelaunayIncremental::new();
+        triangulator.initial_triangulation(&points).unwrap();
+
+        let to_add = triangulator
+            .points_to_add
+            .iter()
+            .map(|idx| *idx as usize)
+            .collect::<Vec<_>>();
+        assert_eq!(to_add, interior);
+    }
+
+    #[test]
+    fn insert_into_existing_mesh() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+            Point::new(0.6, 1.3),
+            Point::new(3.0, 3.0),
+        ];
+        let seed = vec![Triangle::new(0, 1, 2), Triangle::new(0, 2, 3)];
+
+        let mut triangulator = DelaunayIncremental::from_existing(seed, &points).unwrap();
+        triangulator.insert_point(4, &points).unwrap();
+
+        assert_eq!(triangulator.triangles.len(), 4);
+        assert!(triangulator.validate(&points).is_empty());
+        assert!(triangulator
+            .triangles
+            .iter()
+            .filter(|t| [t.index0, t.index1, t.index2].contains(&4))
+            .all(|t| t.area(&points) > 0.0));
+        assert_eq!(
+            triangulator.insert_point(5, &points),
+            Err(TriangulatorError::PointOutsideOfHull)
+        );
+        assert_eq!(
+            triangulator.insert_point(6, &points),
+            Err(TriangulatorError::PointIndexOutOfRange(6))
+        );
+        assert_eq!(triangulator.triangles.len(), 4);
+    }
+
+    #[test]
+    fn existing_mesh_is_checked() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+        ];
+
+        for seed in [
+            vec![Triangle::new(0, 1, 4)],
+            vec![Triangle::new(0, 1, 1)],
+            vec![Triangle::new(0, 1, 2), Triangle::new(2, 1, 0)],
+            // overlapping, on the same side of the edge 0-1
+            vec![Triangle::new(0, 1, 2), Triangle::new(0, 1, 3)],
+            Vec::new(),
+        ] {
+            assert!(matches!(
+                DelaunayIncremental::from_existing(seed, &points),
+                Err(TriangulatorError::InvalidMesh)
+            ));
+        }
+    }
+
+    #[test]
+    fn removing_point_same_as_never_adding_it() {
+        let mut rng = StdRng::seed_from_u64(6);
+
+        for _ in 0..50 {
+            let mut points = (0..30)
+                .map(|_| Point::new(rng.gen_range(0.0..10.0), rng.gen_range(0.0..10.0)))
+                .collect::<Vec<_>>();
+            points.push(Point::new(5.0, 5.0));
+            let removed = (points.len() - 1) as PointIdx;
+
+            let mut triangulator = DelaunayIncremental::new();
+            triangulator.initial_triangulation(&points).unwrap();
+            triangulator.add_all(&points).unwrap();
+            assert!(triangulator.remove_point(removed, &points));
+
+            let expected = triangulate(&points[..points.len() - 1]).unwrap();
+            assert!(triangulator.validate(&points).is_empty());
+            assert_eq!(triangulator.triangles.len(), expected.len());
+            for tri in triangulator.triangles.iter() {
+                assert!(expected.iter().any(|e| e.equivalent(tri)));
+            }
+        }
+    }
+
+    #[test]
+    fn boundary_point_not_removed() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+            Point::new(0.6, 1.3),
+        ];
+
+        let mut triangulator = DelaunayIncremental::new();
+        triangulator.initial_triangulation(&points).unwrap();
+        triangulator.add_all(&points).unwrap();
+
+        assert!(!triangulator.remove_point(0, &points));
+        assert_eq!(triangulator.triangles.len(), 4);
+    }
+
+    #[test]
+    fn bowyer_watson_same_as_hull_based() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for _ in 0..200 {
+            let points = (0..20)
+                .map(|_| Point::new(rng.gen_range(0.0..10.0), rng.gen_range(0.0..10.0)))
+                .collect::<Vec<_>>();
+
+            let expected = triangulate(&points).unwrap();
+            let triangles = triangulate_bowyer_watson(&points).unwrap();
+
+            assert_eq!(triangles.len(), expected.len());
+            for tri in triangles.iter() {
+                assert!(expected.iter().any(|e| e.equivalent(tri)));
+            }
+        }
+    }
+
+    #[test]
+    fn hull_made_delaunay_before_inserting() {
+        // the point added into a fan of the hull doesn't reach all of its non delaunay triangles
+        let points = [
+            Point::new(7.04, 8.0),
+            Point::new(4.0, 2.0),
+            Point::new(5.07, 8.0),
+            Point::new(4.05, 9.0),
+            Point::new(3.05, 5.0),
+            Point::new(9.06, 1.0),
+        ];
+
+        let triangulator = triangulate_with_criterion(&points, FlipCriterion::Delaunay);
+        assert!(triangulator.non_delaunay_edges(&points).is_empty());
+    }
+
+    #[test]
+    fn triangles_flipped_again_after_changing() {
+        // a flip changes both triangles, so the same pair of triangle indices can need another flip later
+        let points = [
+            Point::new(2.6, 4.6),
+            Point::new(5.2, 6.6),
+            Point::new(5.9, 1.0),
+            Point::new(1.5, 2.9),
+            Point::new(8.4, 5.7),
+        ];
+
+        let triangulator = triangulate_with_criterion(&points, FlipCriterion::Delaunay);
+        assert!(triangulator.non_delaunay_edges(&points).is_empty());
+    }
+
+    #[test]
+    fn flips_terminate_on_cocircular_points() {
+        // every quad of a grid is cocircular, so either diagonal passes the circle test
+        let points = (0..64)
+            .map(|i| Point::new((i % 8) as f32, (i / 8) as f32))
+            .collect::<Vec<_>>();
+
+        let triangulator = triangulate_with_criterion(&points, FlipCriterion::Delaunay);
+        assert!(!triangulator.flip_limit_exceeded);
+        assert_eq!(triangulator.triangles.len(), 2 * 7 * 7);
+        assert!(triangulator.validate(&points).is_empty());
+    }
+
+    #[test]
+    fn degenerate_triangle_flipped_only_in_convex_quad() {
+        // 0, 1, 2 are collinear, so the circle of their triangle is infinite
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(1.0, -1.0),
+            Point::new(0.5, -1.0),
+        ];
+        let flat = Triangle::new(0, 2, 1);
+        let convex = Triangle::new(0, 3, 2);
+        // flipping the shared edge 0-1 would give a triangle folded over 1
+        let non_convex = Triangle::new(0, 1, 4);
+        let flip = |a: &Triangle, b: &Triangle| {
+            should_flip(a, b, &points, &[], false, FlipCriterion::Delaunay)
+        };
+
+        assert!(flip(&flat, &convex));
+        assert!(!flip(&flat, &non_convex));
+    }
 }
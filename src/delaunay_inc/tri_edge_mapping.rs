@@ -1,23 +1,53 @@
-use crate::{edge::Edge, types::TriIdx, Triangle};
-use std::collections::{HashMap, HashSet};
+use crate::{
+    edge::Edge,
+    types::{HashMap, HashSet, PointIdx, TriIdx},
+    Triangle,
+};
 
 #[derive(Default, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) struct TriangleEdgeMapping {
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::map_as_list"))]
     edge_tri_map: HashMap<Edge, HashSet<TriIdx>>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::map_as_list"))]
     tri_edge_map: HashMap<TriIdx, HashSet<Edge>>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::map_as_list"))]
+    vertex_tri_map: HashMap<PointIdx, HashSet<TriIdx>>,
 }
 
 impl TriangleEdgeMapping {
     pub fn new() -> Self {
         Self {
-            edge_tri_map: HashMap::new(),
-            tri_edge_map: HashMap::new(),
+            edge_tri_map: HashMap::default(),
+            tri_edge_map: HashMap::default(),
+            vertex_tri_map: HashMap::default(),
         }
     }
 
+    pub fn with_capacity(num_triangles: usize) -> Self {
+        // each triangle has three edges, most of them shared by two triangles
+        Self {
+            edge_tri_map: HashMap::with_capacity_and_hasher(
+                num_triangles * 3 / 2 + 3,
+                Default::default(),
+            ),
+            tri_edge_map: HashMap::with_capacity_and_hasher(num_triangles, Default::default()),
+            vertex_tri_map: HashMap::with_capacity_and_hasher(
+                num_triangles / 2 + 3,
+                Default::default(),
+            ),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.edge_tri_map.clear();
+        self.tri_edge_map.clear();
+        self.vertex_tri_map.clear();
+    }
+
     pub fn add_triangle(&mut self, triangle_index: TriIdx, triangles: &[Triangle]) {
-        let tri = &triangles[triangle_index];
-        let mut edges = HashSet::with_capacity(3);
+        let tri = &triangles[triangle_index as usize];
+        let mut edges = HashSet::with_capacity_and_hasher(3, Default::default());
         edges.insert(Edge::new(tri.index0, tri.index1));
         edges.insert(Edge::new(tri.index1, tri.index2));
         edges.insert(Edge::new(tri.index2, tri.index0));
@@ -27,62 +57,171 @@ impl TriangleEdgeMapping {
         let res = self.tri_edge_map.insert(triangle_index, edges.clone());
         debug_assert!(res.is_none());
 
+        //update vertex-triangle mapping
+        for vertex in [tri.index0, tri.index1, tri.index2] {
+            self.vertex_tri_map
+                .entry(vertex)
+                .or_default()
+                .insert(triangle_index);
+        }
+
         //update edge-triangle mapping
         for edge in edges.into_iter() {
             if let Some(triangle_set) = self.edge_tri_map.get_mut(&edge) {
                 debug_assert!(triangle_set.len() == 1);
                 triangle_set.insert(triangle_index);
             } else {
-                let mut triangle_set = HashSet::with_capacity(1);
+                let mut triangle_set = HashSet::with_capacity_and_hasher(1, Default::default());
                 triangle_set.insert(triangle_index);
                 self.edge_tri_map.insert(edge, triangle_set);
             }
         }
     }
 
-    pub fn remove_triangle(&mut self, triangle_index: TriIdx) {
-        debug_assert!(self.tri_edge_map.contains_key(&triangle_index));
+    /// false if triangle_index isn't mapped
+    pub fn remove_triangle(&mut self, triangle_index: TriIdx) -> bool {
+        let edges = match self.tri_edge_map.remove(&triangle_index) {
+            Some(edges) => edges,
+            None => return false,
+        };
 
         //update edge-triangle mapping
-        for edge in &self.tri_edge_map[&triangle_index] {
+        for edge in &edges {
             if let Some(triangle_set) = self.edge_tri_map.get_mut(edge) {
                 let existed = triangle_set.remove(&triangle_index);
                 debug_assert!(existed);
-            }
-            if self.edge_tri_map.contains_key(edge) && self.edge_tri_map[edge].is_empty() {
-                self.edge_tri_map.remove(edge);
+                if triangle_set.is_empty() {
+                    self.edge_tri_map.remove(edge);
+                }
             }
         }
 
-        //update triangle-edge mapping
-        self.tri_edge_map.remove(&triangle_index);
+        //update vertex-triangle mapping
+        for edge in &edges {
+            for vertex in [edge.index_0, edge.index_1] {
+                if let Some(triangle_set) = self.vertex_tri_map.get_mut(&vertex) {
+                    triangle_set.remove(&triangle_index);
+                    if triangle_set.is_empty() {
+                        self.vertex_tri_map.remove(&vertex);
+                    }
+                }
+            }
+        }
+        true
     }
 
+    /// empty if triangle_index isn't mapped
     pub fn get_edges(&self, triangle_index: TriIdx) -> Vec<&Edge> {
-        self.tri_edge_map[&triangle_index].iter().collect()
+        self.tri_edge_map
+            .get(&triangle_index)
+            .map(|edges| edges.iter().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn num_edges(&self) -> usize {
+        self.edge_tri_map.len()
+    }
+
+    /// vertices used by any triangle
+    pub fn num_vertices(&self) -> usize {
+        self.vertex_tri_map.len()
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = &Edge> {
+        self.edge_tri_map.keys()
     }
 
+    /// false for unknown edges
+    pub fn is_boundary(&self, edge: &Edge) -> bool {
+        self.edge_tri_map
+            .get(edge)
+            .is_some_and(|triangle_set| triangle_set.len() == 1)
+    }
+
+    pub fn contains_edge(&self, edge: &Edge) -> bool {
+        self.edge_tri_map.contains_key(edge)
+    }
+
+    /// empty for unknown edges
     pub fn get_triangles(&self, edge: &Edge) -> Vec<TriIdx> {
-        self.edge_tri_map[edge].iter().copied().collect()
+        self.edge_tri_map
+            .get(edge)
+            .map(|triangle_set| triangle_set.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn get_vertex_triangles(&self, vertex: PointIdx) -> Vec<TriIdx> {
+        self.vertex_tri_map
+            .get(&vertex)
+            .map(|triangle_set| triangle_set.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// triangles whose mapping doesn't match triangles, including mapped triangles that don't exist
+    pub fn inconsistent_triangles(&self, triangles: &[Triangle]) -> Vec<TriIdx> {
+        let mut inconsistent = Vec::new();
+        for (idx, tri) in triangles.iter().enumerate() {
+            let idx = idx as TriIdx;
+            let edges = [
+                Edge::new(tri.index0, tri.index1),
+                Edge::new(tri.index1, tri.index2),
+                Edge::new(tri.index2, tri.index0),
+            ];
+            let consistent = self.tri_edge_map.get(&idx).is_some_and(|mapped| {
+                mapped.len() == 3 && edges.iter().all(|edge| mapped.contains(edge))
+            }) && edges.iter().all(|edge| {
+                self.edge_tri_map
+                    .get(edge)
+                    .is_some_and(|tris| tris.contains(&idx))
+            }) && [tri.index0, tri.index1, tri.index2].iter().all(|vertex| {
+                self.vertex_tri_map
+                    .get(vertex)
+                    .is_some_and(|tris| tris.contains(&idx))
+            });
+
+            if !consistent {
+                inconsistent.push(idx);
+            }
+        }
+
+        let mut stale = self
+            .tri_edge_map
+            .keys()
+            .filter(|idx| **idx as usize >= triangles.len())
+            .copied()
+            .collect::<Vec<_>>();
+        stale.sort_unstable();
+        inconsistent.extend(stale);
+        inconsistent
     }
 
     pub fn neighbouring_triangles(&self, triangle_index: TriIdx) -> Vec<TriIdx> {
         let mut neighbours = Vec::with_capacity(3);
+        self.for_each_neighbour(triangle_index, |tri| neighbours.push(tri));
+        neighbours
+    }
 
-        for edge in self.get_edges(triangle_index) {
-            for tri in self.get_triangles(edge) {
-                if tri != triangle_index {
-                    neighbours.push(tri);
+    /// calls f with each triangle sharing an edge with triangle_index, without allocating
+    pub fn for_each_neighbour(&self, triangle_index: TriIdx, mut f: impl FnMut(TriIdx)) {
+        let edges = match self.tri_edge_map.get(&triangle_index) {
+            Some(edges) => edges,
+            None => return,
+        };
+        for edge in edges {
+            if let Some(triangle_set) = self.edge_tri_map.get(edge) {
+                for tri in triangle_set.iter().copied() {
+                    if tri != triangle_index {
+                        f(tri);
+                    }
                 }
             }
         }
-        neighbours
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::Triangle;
+    use crate::{types::TriIdx, Triangle};
 
     use super::{Edge, TriangleEdgeMapping};
 
@@ -119,6 +258,7 @@ mod tests {
         mapping.remove_triangle(0);
         assert_eq!(mapping.edge_tri_map.len(), 0);
         assert_eq!(mapping.tri_edge_map.len(), 0);
+        assert_eq!(mapping.vertex_tri_map.len(), 0);
     }
 
     #[test]
@@ -174,6 +314,12 @@ mod tests {
         assert!(mapping.tri_edge_map[&1].contains(&Edge::new(2, 3)));
         assert!(mapping.tri_edge_map[&1].contains(&Edge::new(3, 0)));
 
+        assert_eq!(mapping.vertex_tri_map.len(), 3);
+        assert_eq!(mapping.get_vertex_triangles(0), vec![1]);
+        assert!(mapping.get_vertex_triangles(1).is_empty());
+        assert_eq!(mapping.get_vertex_triangles(2), vec![1]);
+        assert_eq!(mapping.get_vertex_triangles(3), vec![1]);
+
         assert_eq!(mapping.edge_tri_map.len(), 3);
         assert!(mapping.edge_tri_map.contains_key(&Edge::new(2, 0)));
         assert!(mapping.edge_tri_map.contains_key(&Edge::new(2, 3)));
@@ -187,4 +333,43 @@ mod tests {
         assert!(mapping.edge_tri_map[&Edge::new(2, 3)].contains(&1));
         assert!(mapping.edge_tri_map[&Edge::new(3, 0)].contains(&1));
     }
+
+    #[test]
+    fn for_each_neighbour_same_as_neighbouring_triangles() {
+        let mut mapping = TriangleEdgeMapping::new();
+        let triangles = [
+            Triangle::new(0, 1, 2),
+            Triangle::new(0, 2, 3),
+            Triangle::new(1, 2, 4),
+        ];
+        for i in 0..triangles.len() {
+            mapping.add_triangle(i as TriIdx, &triangles);
+        }
+
+        let mut visited = Vec::new();
+        mapping.for_each_neighbour(0, |tri| visited.push(tri));
+        visited.sort_unstable();
+
+        assert_eq!(visited, [1, 2]);
+        let mut neighbours = mapping.neighbouring_triangles(0);
+        neighbours.sort_unstable();
+        assert_eq!(neighbours, visited);
+    }
+
+    #[test]
+    fn unknown_keys() {
+        let mut mapping = TriangleEdgeMapping::new();
+        let triangles = [Triangle::new(0, 1, 2)];
+        mapping.add_triangle(0, &triangles);
+
+        assert!(mapping.get_triangles(&Edge::new(0, 3)).is_empty());
+        assert!(mapping.get_edges(1).is_empty());
+        assert!(!mapping.is_boundary(&Edge::new(0, 3)));
+        assert!(mapping.neighbouring_triangles(1).is_empty());
+        assert!(!mapping.remove_triangle(1));
+
+        assert!(mapping.remove_triangle(0));
+        assert!(!mapping.remove_triangle(0));
+        assert!(mapping.get_triangles(&Edge::new(0, 1)).is_empty());
+    }
 }
@@ -0,0 +1,56 @@
+use std::io::{self, Write};
+
+use crate::{Point, Triangle};
+
+/// writes the triangles as dxf 3DFACE entities, with z = 0. only the entities section is written,
+/// which is enough for cad tools to import it
+pub fn write_dxf<W: Write>(w: &mut W, points: &[Point], triangles: &[Triangle]) -> io::Result<()> {
+    writeln!(w, "0\nSECTION\n2\nENTITIES")?;
+
+    for tri in triangles {
+        let corners = [
+            &points[tri.index0 as usize],
+            &points[tri.index1 as usize],
+            &points[tri.index2 as usize],
+            &points[tri.index2 as usize], // 3DFACE always has four corners, triangles repeat the last one
+        ];
+
+        writeln!(w, "0\n3DFACE\n8\n0")?;
+        for (i, p) in corners.iter().enumerate() {
+            writeln!(w, "1{}\n{}\n2{}\n{}\n3{}\n0.0", i, p.x, i, p.y, i)?;
+        }
+    }
+
+    writeln!(w, "0\nENDSEC\n0\nEOF")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::triangulate;
+
+    #[test]
+    fn writes_one_face_per_triangle() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(0.0, 1.0),
+            Point::new(0.4, 0.6),
+        ];
+        let triangles = triangulate(&points).unwrap();
+
+        let mut out = Vec::new();
+        write_dxf(&mut out, &points, &triangles).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines = text.lines().collect::<Vec<_>>();
+
+        assert_eq!(
+            lines.iter().filter(|l| **l == "3DFACE").count(),
+            triangles.len()
+        );
+        assert_eq!(lines[..4], ["0", "SECTION", "2", "ENTITIES"]);
+        assert_eq!(lines[lines.len() - 4..], ["0", "ENDSEC", "0", "EOF"]);
+        assert!(lines.len().is_multiple_of(2)); // group code / value pairs
+    }
+}
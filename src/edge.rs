@@ -1,13 +1,15 @@
 use crate::types::PointIdx;
 
+/// an undirected edge between two points, index_0 is always the lower index
 #[derive(Eq, PartialEq, PartialOrd, Ord, Clone, Copy, Hash)]
-pub(crate) struct Edge {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Edge {
     pub index_0: PointIdx,
     pub index_1: PointIdx,
 }
 
 impl Edge {
-    pub(crate) fn new(index_0: PointIdx, index_1: PointIdx) -> Self {
+    pub fn new(index_0: PointIdx, index_1: PointIdx) -> Self {
         if index_0 < index_1 {
             Self { index_0, index_1 }
         } else {
@@ -19,9 +21,74 @@ impl Edge {
     }
 }
 
+/// an edge from one point to another, eg as part of a triangle's winding. see Triangle::directed_edges
+#[derive(Eq, PartialEq, PartialOrd, Ord, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DirectedEdge {
+    pub from: PointIdx,
+    pub to: PointIdx,
+}
+
+impl DirectedEdge {
+    pub fn new(from: PointIdx, to: PointIdx) -> Self {
+        Self { from, to }
+    }
+
+    pub fn reversed(&self) -> Self {
+        Self::new(self.to, self.from)
+    }
+
+    /// the same edge without direction, eg to look it up in the triangulation
+    pub fn undirected(&self) -> Edge {
+        Edge::new(self.from, self.to)
+    }
+}
+
 // Terser than derived debug
 impl core::fmt::Debug for Edge {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "({}, {})", self.index_0, self.index_1)
     }
 }
+
+impl core::fmt::Debug for DirectedEdge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({} -> {})", self.from, self.to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{point::cross, Point, Triangle};
+
+    #[test]
+    fn directed_edges_wind_consistently() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(0.5, 1.0),
+        ];
+        let tri = Triangle::new(0, 1, 2);
+
+        let edges = tri.directed_edges();
+
+        assert_eq!(
+            edges,
+            [
+                DirectedEdge::new(0, 1),
+                DirectedEdge::new(1, 2),
+                DirectedEdge::new(2, 0)
+            ]
+        );
+        for (i, edge) in edges.iter().enumerate() {
+            let next = edges[(i + 1) % 3];
+            assert_eq!(edge.to, next.from);
+            // counter clockwise, so the opposite vertex is on the left of each edge
+            let (from, to) = (&points[edge.from as usize], &points[edge.to as usize]);
+            assert!(cross(to, &points[next.to as usize], from) > 0.0);
+        }
+        assert_eq!(edges[0].reversed(), DirectedEdge::new(1, 0));
+        assert_eq!(edges[2].undirected(), Edge::new(0, 2));
+    }
+}
@@ -0,0 +1,157 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+
+use crate::{types::PointIdx, ParseError, Triangle};
+
+/// reads triangles in the .ele format of shewchuk's triangle:
+/// a header `<#triangles> <nodes per triangle> <#attributes>`, then `<triangle #> <node> <node> <node> ...` per line.
+/// `#` starts a comment. indices in the file are 1-based unless the first triangle is numbered 0 (triangle -z),
+/// they are converted to 0-based. for 6 node (quadratic) triangles only the corners are read
+pub fn read_ele<R: Read>(r: R) -> Result<Vec<Triangle>, ParseError> {
+    let mut lines = Vec::new();
+    for (line_idx, line) in BufReader::new(r).lines().enumerate() {
+        let line = line?;
+        let content = line.split('#').next().unwrap_or("");
+        let fields = content
+            .split_whitespace()
+            .map(str::to_owned)
+            .collect::<Vec<_>>();
+        if !fields.is_empty() {
+            lines.push((line_idx + 1, fields));
+        }
+    }
+
+    let mut lines = lines.into_iter();
+    let (count, nodes_per_triangle) = match lines.next() {
+        Some((_, header)) if header.len() >= 2 => {
+            let count = header[0].parse::<usize>();
+            let nodes = header[1].parse::<usize>();
+            match (count, nodes) {
+                (Ok(count), Ok(nodes)) if nodes >= 3 => (count, nodes),
+                _ => return Err(ParseError::InvalidHeader),
+            }
+        }
+        _ => return Err(ParseError::InvalidHeader),
+    };
+
+    let mut triangles = Vec::with_capacity(count);
+    let mut base = None;
+    for (line_no, fields) in lines.take(count) {
+        if fields.len() < 1 + nodes_per_triangle {
+            return Err(ParseError::InvalidLine(line_no));
+        }
+        let indices = fields[..4]
+            .iter()
+            .map(|f| f.parse::<usize>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| ParseError::InvalidLine(line_no))?;
+
+        let base = *base.get_or_insert(indices[0].min(1));
+        let corner = |i: usize| {
+            indices[i]
+                .checked_sub(base)
+                .map(|idx| idx as PointIdx)
+                .ok_or(ParseError::InvalidLine(line_no))
+        };
+        triangles.push(Triangle::new(corner(1)?, corner(2)?, corner(3)?));
+    }
+
+    if triangles.len() != count {
+        return Err(ParseError::WrongCount {
+            expected: count,
+            found: triangles.len(),
+        });
+    }
+    Ok(triangles)
+}
+
+/// writes triangles in the .ele format of shewchuk's triangle, with no attributes.
+/// the file format is 1-based, so every index is written as its 0-based index + 1, the triangle numbers too.
+/// read_ele converts them back
+pub fn write_ele<W: Write>(w: &mut W, triangles: &[Triangle]) -> io::Result<()> {
+    writeln!(w, "{} 3 0", triangles.len())?;
+    for (i, tri) in triangles.iter().enumerate() {
+        writeln!(
+            w,
+            "{} {} {} {}",
+            i + 1,
+            tri.index0 as usize + 1,
+            tri.index1 as usize + 1,
+            tri.index2 as usize + 1
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn corners(triangles: &[Triangle]) -> Vec<[PointIdx; 3]> {
+        triangles
+            .iter()
+            .map(|t| [t.index0, t.index1, t.index2])
+            .collect()
+    }
+
+    #[test]
+    fn reads_one_based_sample() {
+        let sample = "# square split in two
+2 3 0
+1 1 2 3
+2    1 3 4   # trailing comment
+
+";
+
+        let triangles = read_ele(sample.as_bytes()).unwrap();
+
+        assert_eq!(corners(&triangles), [[0, 1, 2], [0, 2, 3]]);
+    }
+
+    #[test]
+    fn reads_zero_based_with_attributes() {
+        let sample = "1 6 1\n0 0 1 2 3 4 5 7.5\n";
+
+        let triangles = read_ele(sample.as_bytes()).unwrap();
+
+        assert_eq!(corners(&triangles), [[0, 1, 2]]);
+    }
+
+    #[test]
+    fn write_then_read_round_trips() {
+        let triangles = [
+            Triangle::new(0, 1, 2),
+            Triangle::new(2, 1, 3),
+            Triangle::new(3, 4, 2),
+        ];
+        let mut buf = Vec::new();
+
+        write_ele(&mut buf, &triangles).unwrap();
+
+        assert!(String::from_utf8_lossy(&buf).starts_with("3 3 0\n1 1 2 3\n"));
+        let read = read_ele(buf.as_slice()).unwrap();
+        assert_eq!(corners(&read), corners(&triangles));
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        assert!(matches!(
+            read_ele("".as_bytes()),
+            Err(ParseError::InvalidHeader)
+        ));
+        assert!(matches!(
+            read_ele("2 3 0\n1 1 2 x\n".as_bytes()),
+            Err(ParseError::InvalidLine(2))
+        ));
+        assert!(matches!(
+            read_ele("1 3 0\n1 1 0 2\n".as_bytes()),
+            Err(ParseError::InvalidLine(2))
+        ));
+        assert!(matches!(
+            read_ele("2 3 0\n1 1 2 3\n".as_bytes()),
+            Err(ParseError::WrongCount {
+                expected: 2,
+                found: 1
+            })
+        ));
+    }
+}
@@ -0,0 +1,68 @@
+use crate::Point;
+
+/// largest coordinate magnitude for exact predicates. larger integers aren't exactly representable as f32,
+/// and the in-circle determinant would overflow i128 somewhat above it
+pub(crate) const MAX_EXACT_COORD: i32 = 1 << 24;
+
+/// exact sign of cross(a, b, origin), as -1, 0 or 1, for points with integral coordinates
+pub(crate) fn orient(a: &Point, b: &Point, origin: &Point) -> f32 {
+    let (ax, ay) = (a.x as i64 - origin.x as i64, a.y as i64 - origin.y as i64);
+    let (bx, by) = (b.x as i64 - origin.x as i64, b.y as i64 - origin.y as i64);
+    (ax * by - ay * bx).signum() as f32
+}
+
+/// exact in-circle test for points with integral coordinates. on the circle counts as inside,
+/// and collinear a, b, c as containing everything, like the float version
+pub(crate) fn in_circle(a: &Point, b: &Point, c: &Point, point: &Point) -> bool {
+    let orientation = orient(b, c, a);
+    if orientation == 0.0 {
+        return true;
+    }
+
+    let lift = |p: &Point| {
+        let x = p.x as i128 - point.x as i128;
+        let y = p.y as i128 - point.y as i128;
+        (x, y, x * x + y * y)
+    };
+    let (ax, ay, a_sqr) = lift(a);
+    let (bx, by, b_sqr) = lift(b);
+    let (cx, cy, c_sqr) = lift(c);
+
+    let det = ax * (by * c_sqr - b_sqr * cy) - ay * (bx * c_sqr - b_sqr * cx)
+        + a_sqr * (bx * cy - by * cx);
+    det.signum() as f32 * orientation >= 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orient_matches_cross_sign() {
+        let a = Point::new(0.0, 0.0);
+        let b = Point::new(4.0, 0.0);
+        assert_eq!(orient(&b, &Point::new(2.0, 3.0), &a), 1.0);
+        assert_eq!(orient(&b, &Point::new(2.0, -3.0), &a), -1.0);
+        assert_eq!(orient(&b, &Point::new(8.0, 0.0), &a), 0.0);
+    }
+
+    #[test]
+    fn in_circle_at_large_coordinates() {
+        let big = (MAX_EXACT_COORD - 1) as f32;
+        let a = Point::new(-big, -big);
+        let b = Point::new(big, -big);
+        let c = Point::new(big, big);
+
+        // the fourth corner of the square is exactly on the circle
+        assert!(in_circle(&a, &b, &c, &Point::new(-big, big)));
+        assert!(in_circle(&c, &b, &a, &Point::new(-big, big)));
+
+        // a tiny triangle far out, where squaring coordinates in f32 loses all precision
+        let a = Point::new(-big, -big);
+        let b = Point::new(-big + 2.0, -big);
+        let c = Point::new(-big, -big + 2.0);
+        assert!(in_circle(&a, &b, &c, &Point::new(-big + 2.0, -big + 2.0)));
+        assert!(in_circle(&a, &b, &c, &Point::new(-big + 1.0, -big + 1.0)));
+        assert!(!in_circle(&a, &b, &c, &Point::new(-big + 3.0, -big)));
+    }
+}
@@ -0,0 +1,184 @@
+use crate::{types::PointIdx, Point};
+
+/// the order in which points inside the hull are inserted.
+/// in exact arithmetic the final delaunay triangulation doesn't depend on the order,
+/// but with floating point it can change how ties (eg cocircular points) are broken
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InsertionOrder {
+    /// the reverse of the order the convex hull computation leaves the inner points in
+    #[default]
+    FromHull,
+
+    /// in order of increasing point index
+    AsGiven,
+
+    /// shuffled, reproducible for a given seed
+    Random(u64),
+
+    /// along a hilbert curve, so consecutive points are close to each other, see spatial_sort
+    SpatialSort,
+}
+
+/// orders indices as they should be inserted, first to last
+pub(crate) fn order_indices(indices: &mut [PointIdx], points: &[Point], order: InsertionOrder) {
+    match order {
+        // the hull's points are popped from the back
+        InsertionOrder::FromHull => indices.reverse(),
+        InsertionOrder::AsGiven => indices.sort_unstable(),
+        InsertionOrder::Random(seed) => {
+            // sort first, so the result only depends on the seed and not on the incoming order
+            indices.sort_unstable();
+            shuffle(indices, seed);
+        }
+        InsertionOrder::SpatialSort => hilbert_sort(indices, points),
+    }
+}
+
+// fisher-yates with splitmix64
+pub(crate) fn shuffle(indices: &mut [PointIdx], seed: u64) {
+    let mut state = seed;
+    let mut next = || {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    };
+
+    for i in (1..indices.len()).rev() {
+        let j = (next() % (i as u64 + 1)) as usize;
+        indices.swap(i, j);
+    }
+}
+
+/// indices of points ordered along a hilbert curve over their bounding box.
+/// nearby points tend to be near in the order, more so than with a z (morton) curve,
+/// which is useful for cache friendly iteration over per point data
+pub fn spatial_sort(points: &[Point]) -> Vec<PointIdx> {
+    let mut indices = (0..points.len() as PointIdx).collect::<Vec<_>>();
+    hilbert_sort(&mut indices, points);
+    indices
+}
+
+// along the hilbert curve over the bounding box of the indexed points, ties by index
+fn hilbert_sort(indices: &mut [PointIdx], points: &[Point]) {
+    let to_grid = grid_coords(indices, points);
+    indices.sort_by_key(|idx| {
+        let (x, y) = to_grid(&points[*idx as usize]);
+        (hilbert_index(x, y), *idx)
+    });
+}
+
+// maps points to a 65536 x 65536 grid over the bounding box of the indexed points
+fn grid_coords(indices: &[PointIdx], points: &[Point]) -> impl Fn(&Point) -> (u32, u32) {
+    let (mut min_x, mut min_y) = (f32::MAX, f32::MAX);
+    let (mut max_x, mut max_y) = (f32::MIN, f32::MIN);
+    for idx in indices.iter() {
+        let p = &points[*idx as usize];
+        min_x = min_x.min(p.x);
+        min_y = min_y.min(p.y);
+        max_x = max_x.max(p.x);
+        max_y = max_y.max(p.y);
+    }
+    let scale_x = if max_x > min_x {
+        65535.0 / (max_x - min_x)
+    } else {
+        0.0
+    };
+    let scale_y = if max_y > min_y {
+        65535.0 / (max_y - min_y)
+    } else {
+        0.0
+    };
+
+    move |p: &Point| {
+        (
+            ((p.x - min_x) * scale_x) as u32,
+            ((p.y - min_y) * scale_y) as u32,
+        )
+    }
+}
+
+// distance along the hilbert curve filling the 65536 x 65536 grid
+fn hilbert_index(mut x: u32, mut y: u32) -> u64 {
+    const N: u32 = 1 << 16;
+    let mut d = 0;
+    let mut s = N / 2;
+    while s > 0 {
+        let rx = (x & s > 0) as u32;
+        let ry = (y & s > 0) as u32;
+        d += s as u64 * s as u64 * ((3 * rx) ^ ry) as u64;
+
+        // rotate the quadrant so the curve inside it starts and ends at the right corners
+        if ry == 0 {
+            if rx == 1 {
+                x = N - 1 - x;
+                y = N - 1 - y;
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+        s /= 2;
+    }
+    d
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_order() {
+        let points = vec![Point::new(0.0, 0.0); 10];
+        let mut a = (0..10).collect::<Vec<_>>();
+        let mut b = (0..10).rev().collect::<Vec<_>>();
+
+        order_indices(&mut a, &points, InsertionOrder::Random(7));
+        order_indices(&mut b, &points, InsertionOrder::Random(7));
+
+        assert_eq!(a, b);
+        assert_ne!(a, (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn hilbert_order_is_local_permutation() {
+        let points = (0..256)
+            .map(|i| Point::new((i % 16) as f32, (i / 16) as f32))
+            .collect::<Vec<_>>();
+
+        let order = spatial_sort(&points);
+
+        let mut sorted = order.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..256).collect::<Vec<_>>());
+
+        // along a hilbert curve, consecutive points are grid neighbours most of the time
+        let steps = order
+            .windows(2)
+            .map(|w| {
+                let d = points[w[1] as usize] - points[w[0] as usize];
+                (d.x * d.x + d.y * d.y).sqrt()
+            })
+            .collect::<Vec<_>>();
+        let neighbours = steps.iter().filter(|d| **d == 1.0).count();
+        assert!(neighbours > 200, "{}", neighbours);
+        assert!(steps.iter().sum::<f32>() < 1.5 * 255.0);
+    }
+
+    #[test]
+    fn spatial_sort_order_follows_hilbert_curve() {
+        let points = [
+            Point::new(1.0, 1.0),
+            Point::new(0.0, 0.0),
+            Point::new(0.0, 1.0),
+            Point::new(1.0, 0.0),
+        ];
+        let mut indices = vec![0, 1, 2, 3];
+
+        order_indices(&mut indices, &points, InsertionOrder::SpatialSort);
+
+        // up the left side, across and down the right, unlike z order which goes across first
+        assert_eq!(indices, vec![1, 2, 0, 3]);
+        assert_eq!(spatial_sort(&points), indices);
+    }
+}
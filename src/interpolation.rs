@@ -0,0 +1,374 @@
+use std::cmp::Ordering;
+
+use crate::{
+    circle::{circumcenter, Circle},
+    edge::Edge,
+    types::{HashMap, PointIdx},
+    Point, Triangle,
+};
+
+// tolerance for a point on an edge to count as inside a triangle
+const BARYCENTRIC_EPSILON: f32 = 1e-6;
+
+/// finds a triangle containing point, along with the barycentric coordinates of point in it
+pub(crate) fn locate(
+    triangles: &[Triangle],
+    points: &[Point],
+    point: &Point,
+) -> Option<(usize, [f32; 3])> {
+    triangles.iter().enumerate().find_map(|(idx, tri)| {
+        let weights = tri.barycentric(points, point)?;
+        if weights.iter().all(|w| *w >= -BARYCENTRIC_EPSILON) {
+            Some((idx, weights))
+        } else {
+            None
+        }
+    })
+}
+
+/// piecewise linear interpolation of values, given per point
+pub(crate) fn linear(
+    triangles: &[Triangle],
+    points: &[Point],
+    values: &[f32],
+    query: &Point,
+) -> Option<f32> {
+    if values.len() != points.len() {
+        return None;
+    }
+
+    let (tri_idx, weights) = locate(triangles, points, query)?;
+    let tri = &triangles[tri_idx];
+    Some(
+        weights[0] * values[tri.index0 as usize]
+            + weights[1] * values[tri.index1 as usize]
+            + weights[2] * values[tri.index2 as usize],
+    )
+}
+
+/// natural neighbour (Sibson) interpolation of values, given per point.
+/// the weight of each neighbour is the area its voronoi cell would lose if query was inserted.
+/// falls back to linear interpolation when query is on the hull boundary, where the stolen areas are degenerate
+pub(crate) fn natural_neighbour(
+    triangles: &[Triangle],
+    points: &[Point],
+    values: &[f32],
+    query: &Point,
+) -> Option<f32> {
+    if values.len() != points.len() {
+        return None;
+    }
+
+    let (tri_idx, _) = locate(triangles, points, query)?;
+    let tri = &triangles[tri_idx];
+    for idx in [tri.index0, tri.index1, tri.index2] {
+        let p = &points[idx as usize];
+        if (p.x - query.x).abs() < f32::EPSILON && (p.y - query.y).abs() < f32::EPSILON {
+            return Some(values[idx as usize]);
+        }
+    }
+
+    // the cavity, ie triangles that would be removed when inserting query
+    let cavity = triangles
+        .iter()
+        .filter(|tri| Circle::from_triangle(tri, points).contains(query))
+        .collect::<Vec<_>>();
+
+    let mut edge_count = HashMap::<Edge, usize>::default();
+    for tri in cavity.iter() {
+        for edge in tri_edges(tri) {
+            *edge_count.entry(edge).or_insert(0) += 1;
+        }
+    }
+
+    // the stolen region of each neighbour is bounded by the circumcenters of its cavity triangles
+    // and the circumcenters of the new triangles it would be part of
+    let mut stolen_regions = HashMap::<PointIdx, Vec<Point>>::default();
+    for tri in cavity.iter() {
+        let center = circumcenter(
+            &points[tri.index0 as usize],
+            &points[tri.index1 as usize],
+            &points[tri.index2 as usize],
+        );
+        for idx in [tri.index0, tri.index1, tri.index2] {
+            stolen_regions.entry(idx).or_default().push(center);
+        }
+    }
+    for (edge, _) in edge_count.iter().filter(|(_, count)| **count == 1) {
+        let center = circumcenter(
+            query,
+            &points[edge.index_0 as usize],
+            &points[edge.index_1 as usize],
+        );
+        stolen_regions.entry(edge.index_0).or_default().push(center);
+        stolen_regions.entry(edge.index_1).or_default().push(center);
+    }
+
+    let mut weighted_sum = 0.0;
+    let mut area_sum = 0.0;
+    for (idx, region) in stolen_regions.iter_mut() {
+        let area = convex_polygon_area(region);
+        weighted_sum += area * values[*idx as usize] as f64;
+        area_sum += area;
+    }
+
+    if !weighted_sum.is_finite() || area_sum <= 0.0 {
+        return linear(triangles, points, values, query);
+    }
+    Some((weighted_sum / area_sum) as f32)
+}
+
+/// the gradient of the piecewise linear field given by values per point, constant on each triangle.
+/// zero for degenerate triangles, empty if values doesn't have one value per point
+pub fn gradients(points: &[Point], triangles: &[Triangle], values: &[f32]) -> Vec<Point> {
+    if values.len() != points.len() {
+        return Vec::new();
+    }
+
+    triangles
+        .iter()
+        .map(|tri| {
+            let (a, b, c) = (
+                &points[tri.index0 as usize],
+                &points[tri.index1 as usize],
+                &points[tri.index2 as usize],
+            );
+            let db = values[tri.index1 as usize] - values[tri.index0 as usize];
+            let dc = values[tri.index2 as usize] - values[tri.index0 as usize];
+
+            // solve [b - a; c - a] * gradient = [db; dc]
+            let det = (b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y);
+            if det == 0.0 {
+                return Point::new(0.0, 0.0);
+            }
+            Point::new(
+                (db * (c.y - a.y) - dc * (b.y - a.y)) / det,
+                (dc * (b.x - a.x) - db * (c.x - a.x)) / det,
+            )
+        })
+        .collect()
+}
+
+/// unit normals of the surface given by heights per point, for smooth shading. each is the average of the normals
+/// of the triangles around the point, weighted by their area, and points upwards. [0, 0, 1] for points not in any
+/// triangle, empty if heights doesn't have one height per point
+pub fn vertex_normals(points: &[Point], heights: &[f32], triangles: &[Triangle]) -> Vec<[f32; 3]> {
+    if heights.len() != points.len() {
+        return Vec::new();
+    }
+
+    let mut sums = vec![[0.0_f32; 3]; points.len()];
+    for tri in triangles {
+        let indices = [
+            tri.index0 as usize,
+            tri.index1 as usize,
+            tri.index2 as usize,
+        ];
+        let [a, b, c] = indices;
+        let u = [
+            points[b].x - points[a].x,
+            points[b].y - points[a].y,
+            heights[b] - heights[a],
+        ];
+        let v = [
+            points[c].x - points[a].x,
+            points[c].y - points[a].y,
+            heights[c] - heights[a],
+        ];
+
+        // the cross product is twice the area long, so adding them up weights by area
+        let mut normal = [
+            u[1] * v[2] - u[2] * v[1],
+            u[2] * v[0] - u[0] * v[2],
+            u[0] * v[1] - u[1] * v[0],
+        ];
+        if normal[2] < 0.0 {
+            normal = [-normal[0], -normal[1], -normal[2]];
+        }
+        for idx in indices {
+            for (sum, n) in sums[idx].iter_mut().zip(normal.iter()) {
+                *sum += n;
+            }
+        }
+    }
+
+    sums.into_iter()
+        .map(|[x, y, z]| {
+            let length = (x * x + y * y + z * z).sqrt();
+            if length > 0.0 {
+                [x / length, y / length, z / length]
+            } else {
+                [0.0, 0.0, 1.0]
+            }
+        })
+        .collect()
+}
+
+fn tri_edges(tri: &Triangle) -> [Edge; 3] {
+    [
+        Edge::new(tri.index0, tri.index1),
+        Edge::new(tri.index1, tri.index2),
+        Edge::new(tri.index2, tri.index0),
+    ]
+}
+
+// area of a convex polygon given its vertices in any order
+fn convex_polygon_area(vertices: &mut [Point]) -> f64 {
+    let n = vertices.len() as f64;
+    let cx = vertices.iter().map(|p| p.x as f64).sum::<f64>() / n;
+    let cy = vertices.iter().map(|p| p.y as f64).sum::<f64>() / n;
+    let angle = |p: &Point| (p.y as f64 - cy).atan2(p.x as f64 - cx);
+    vertices.sort_by(|a, b| angle(a).partial_cmp(&angle(b)).unwrap_or(Ordering::Equal));
+
+    let mut area = 0.0;
+    for i in 0..vertices.len() {
+        let a = &vertices[i];
+        let b = &vertices[(i + 1) % vertices.len()];
+        area += a.x as f64 * b.y as f64 - b.x as f64 * a.y as f64;
+    }
+    0.5 * area.abs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::triangulate;
+
+    #[test]
+    fn linear_reproduces_plane() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+            Point::new(0.7, 1.2),
+        ];
+        let plane = |p: &Point| 3.0 * p.x - 2.0 * p.y + 1.0;
+        let values = points.iter().map(plane).collect::<Vec<_>>();
+        let triangles = triangulate(&points).unwrap();
+
+        for query in [
+            Point::new(0.5, 0.5),
+            Point::new(1.5, 0.2),
+            Point::new(1.9, 1.9),
+            Point::new(0.0, 1.0),
+        ] {
+            let value = linear(&triangles, &points, &values, &query).unwrap();
+            assert!((value - plane(&query)).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn linear_outside_hull() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.0, 1.0),
+        ];
+        let values = [1.0, 2.0, 3.0];
+        let triangles = triangulate(&points).unwrap();
+
+        assert_eq!(
+            linear(&triangles, &points, &values, &Point::new(1.0, 1.0)),
+            None
+        );
+        assert_eq!(
+            linear(&triangles, &points, &values[..2], &Point::new(0.1, 0.1)),
+            None
+        );
+    }
+
+    #[test]
+    fn gradient_of_plane_is_constant() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+            Point::new(0.7, 1.2),
+            Point::new(1.4, 0.6),
+        ];
+        let plane = |p: &Point| 3.0 * p.x - 2.0 * p.y + 1.0;
+        let values = points.iter().map(plane).collect::<Vec<_>>();
+        let triangles = triangulate(&points).unwrap();
+
+        let grads = gradients(&points, &triangles, &values);
+
+        assert_eq!(grads.len(), triangles.len());
+        for grad in grads {
+            assert!((grad.x - 3.0).abs() < 1e-5 && (grad.y + 2.0).abs() < 1e-5);
+        }
+        assert!(gradients(&points, &triangles, &values[..2]).is_empty());
+    }
+
+    #[test]
+    fn normals_of_flat_field_point_up() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+            Point::new(0.7, 1.2),
+            Point::new(1.4, 0.6),
+        ];
+        let triangles = triangulate(&points).unwrap();
+
+        let normals = vertex_normals(&points, &[1.5; 6], &triangles);
+
+        assert_eq!(normals.len(), points.len());
+        assert!(normals.iter().all(|n| *n == [0.0, 0.0, 1.0]));
+
+        let plane = |p: &Point| 3.0 * p.x - 2.0 * p.y + 1.0;
+        let heights = points.iter().map(plane).collect::<Vec<_>>();
+        let length = 14.0_f32.sqrt();
+        for n in vertex_normals(&points, &heights, &triangles) {
+            assert!((n[0] + 3.0 / length).abs() < 1e-5);
+            assert!((n[1] - 2.0 / length).abs() < 1e-5);
+            assert!((n[2] - 1.0 / length).abs() < 1e-5);
+        }
+        assert!(vertex_normals(&points, &heights[..2], &triangles).is_empty());
+    }
+
+    #[test]
+    fn natural_neighbour_at_vertex() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+            Point::new(0.7, 1.2),
+        ];
+        let values = [1.0, 5.0, -2.0, 3.0, 7.0];
+        let triangles = triangulate(&points).unwrap();
+
+        for (point, value) in points.iter().zip(values.iter()) {
+            let res = natural_neighbour(&triangles, &points, &values, point).unwrap();
+            assert!((res - value).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn natural_neighbour_reproduces_plane() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+            Point::new(0.7, 1.2),
+            Point::new(1.4, 0.6),
+        ];
+        let plane = |p: &Point| 3.0 * p.x - 2.0 * p.y + 1.0;
+        let values = points.iter().map(plane).collect::<Vec<_>>();
+        let triangles = triangulate(&points).unwrap();
+
+        for query in [
+            Point::new(0.5, 0.5),
+            Point::new(1.5, 0.2),
+            Point::new(1.0, 1.0),
+            Point::new(2.0, 1.0),
+        ] {
+            let value = natural_neighbour(&triangles, &points, &values, &query).unwrap();
+            assert!((value - plane(&query)).abs() < 1e-3);
+        }
+    }
+}
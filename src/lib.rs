@@ -23,18 +23,79 @@
 //!    }
 //!```
 
+// PointIdx and TriIdx are usize unless the small-indices feature is enabled, so index casts are needed
+#![allow(clippy::unnecessary_cast)]
+
+mod alpha;
+mod boundary;
+mod builder;
 mod circle;
+mod conditioning;
+mod contour;
 mod convex_hull;
+#[cfg(feature = "csv")]
+mod csv;
+mod decimate;
 mod delaunay_inc;
+#[cfg(feature = "dxf")]
+mod dxf;
 mod edge;
+mod ele;
+mod exact;
+mod insertion_order;
+mod interpolation;
+mod lloyd;
+mod options;
 mod point;
+mod polygon;
+mod pslg;
+mod quality;
+#[cfg(feature = "image")]
+mod raster;
+mod segment;
+mod spacing;
+mod streaming;
+mod strip;
+mod svg;
 mod triangle;
+mod triangulation;
 mod types;
 mod utils;
+mod voronoi;
+#[cfg(feature = "wasm")]
+mod wasm;
 
+pub use alpha::alpha_complex;
+pub use boundary::{boundary_edges, boundary_polygon};
+pub use builder::TriangulatorBuilder;
 pub use circle::Circle;
-pub use point::Point;
+pub use conditioning::{analyze_conditioning, Conditioning, Recommendation};
+pub use contour::contour;
+pub use convex_hull::{extreme_points, interior_points};
+#[cfg(feature = "csv")]
+pub use csv::read_points_csv;
+#[cfg(feature = "dxf")]
+pub use dxf::write_dxf;
+pub use edge::{DirectedEdge, Edge};
+pub use ele::{read_ele, write_ele};
+pub use insertion_order::{spatial_sort, InsertionOrder};
+pub use interpolation::{gradients, vertex_normals};
+pub use lloyd::lloyd_relax;
+pub use options::{FlipCriterion, TriangulatorOptions};
+pub use point::{orientation, Orientation, Point};
+pub use polygon::{polygon_winding, Winding};
+pub use pslg::ConformingTriangulation;
+pub use quality::quality_colors;
+#[cfg(feature = "image")]
+pub use raster::{rasterize, rasterize_with, RasterStyle};
+pub use segment::segments_intersect;
+pub use spacing::{filter_outliers, nearest_spacing};
+pub use streaming::StreamingTriangulator;
+pub use strip::to_triangle_strips;
+pub use svg::write_voronoi_svg;
 pub use triangle::Triangle;
+pub use triangulation::{FullTriangulation, Topology, Triangulation};
+pub use types::{Neighbours, PointIdx, TriIdx};
 
 use delaunay_inc::DelaunayIncremental;
 
@@ -48,6 +109,95 @@ pub enum TriangulatorError {
 
     #[error("Point found outside of hull")]
     PointOutsideOfHull,
+
+    #[error("Number of weights differs from number of points")]
+    WeightCountMismatch,
+
+    #[error("Coordinate too large for exact predicates, index {0}")]
+    CoordinateOutOfRange(usize),
+
+    #[error("Edge flipping did not terminate")]
+    FlipLimitExceeded,
+
+    #[error("Segment {0} has an unknown or repeated point index")]
+    InvalidSegment(usize),
+
+    #[error("Given triangles don't form a valid mesh of the points")]
+    InvalidMesh,
+
+    #[error("Polygon is not simple, no ear could be clipped")]
+    NotSimplePolygon,
+
+    #[error("Points {0} and {1} coincide")]
+    CoincidentPoints(usize, usize),
+
+    #[error("All points are collinear")]
+    Collinear,
+
+    #[error("Point {0} is outside of the bounds")]
+    PointOutsideOfBounds(usize),
+
+    #[error("Point index {0} is out of range")]
+    PointIndexOutOfRange(usize),
+
+    #[error("Minimum of the bounds is greater than the maximum")]
+    InvalidBounds,
+}
+
+/// a broken invariant found by Triangulator::validate
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum ValidationIssue {
+    #[error("Triangle {0} has zero area")]
+    DegenerateTriangle(TriIdx),
+
+    #[error("Triangle {1} has the same vertices as triangle {0}")]
+    DuplicateTriangle(TriIdx, TriIdx),
+
+    #[error("Edge {0:?} is shared by more than two triangles")]
+    EdgeOverused(Edge),
+
+    #[error("Edge mapping of triangle {0} doesn't match the triangle")]
+    InconsistentMapping(TriIdx),
+}
+
+/// errors reading mesh files, eg read_ele
+#[derive(Debug, thiserror::Error)]
+pub enum ParseError {
+    #[error("Read failed: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Missing or invalid header")]
+    InvalidHeader,
+
+    #[error("Invalid entry on line {0}")]
+    InvalidLine(usize),
+
+    #[error("Header gives {expected} entries, found {found}")]
+    WrongCount { expected: usize, found: usize },
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum FlipError {
+    #[error("Edge not found in triangulation")]
+    UnknownEdge,
+
+    #[error("Edge is on the boundary")]
+    BoundaryEdge,
+
+    #[error("Triangles of edge form a non-convex quad")]
+    NonConvexQuad,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum CollapseError {
+    #[error("Edge not found in triangulation")]
+    UnknownEdge,
+
+    #[error("Collapse would invert a triangle")]
+    InvertsTriangle,
+
+    #[error("Collapse would make the mesh non-manifold")]
+    NonManifold,
 }
 
 pub fn triangulate(points: &[Point]) -> Result<Vec<Triangle>, TriangulatorError> {
@@ -59,7 +209,189 @@ pub fn triangulate(points: &[Point]) -> Result<Vec<Triangle>, TriangulatorError>
     Ok(triangles)
 }
 
+/// same result as triangulate, but built the classic bowyer-watson way,
+/// inserting all points into a triangle enclosing them instead of starting from the convex hull
+pub fn triangulate_bowyer_watson(points: &[Point]) -> Result<Vec<Triangle>, TriangulatorError> {
+    if points.len() < 3 {
+        return Err(TriangulatorError::TooFewPoints);
+    }
+
+    delaunay_inc::triangulate_bowyer_watson(points)
+}
+
+/// weighted (power) delaunay triangulation, where each point has a weight, eg its radius squared.
+/// the power distance |p - x|^2 - weight replaces the euclidean distance. with equal weights this is the same as triangulate.
+/// points whose power cell would be empty (redundant points) are still part of the triangulation
+pub fn triangulate_weighted(
+    points: &[Point],
+    weights: &[f32],
+) -> Result<Vec<Triangle>, TriangulatorError> {
+    if points.len() < 3 {
+        return Err(TriangulatorError::TooFewPoints);
+    }
+    if weights.len() != points.len() {
+        return Err(TriangulatorError::WeightCountMismatch);
+    }
+
+    delaunay_inc::triangulate_weighted(points, weights)
+}
+
+/// same as triangulate, but writes the triangles into out, reusing its allocation. out is cleared first.
+pub fn triangulate_into(
+    points: &[Point],
+    out: &mut Vec<Triangle>,
+) -> Result<(), TriangulatorError> {
+    out.clear();
+    if points.len() < 3 {
+        return Err(TriangulatorError::TooFewPoints);
+    }
+
+    delaunay_inc::triangulate_into(points, out)
+}
+
+/// triangulation of integer points, using exact integer arithmetic for all geometric predicates,
+/// so that it is robust for grid data with many collinear and cocircular points.
+/// coordinates must be within +-2^24 (16777216), beyond that they aren't exact in the
+/// internal f32 points and the determinants could overflow. gives CoordinateOutOfRange otherwise
+pub fn triangulate_i32(points: &[(i32, i32)]) -> Result<Vec<Triangle>, TriangulatorError> {
+    if points.len() < 3 {
+        return Err(TriangulatorError::TooFewPoints);
+    }
+
+    delaunay_inc::triangulate_i32(points)
+}
+
+/// same as triangulate, but also returns the neighbour of each triangle across each of its edges.
+/// neighbours[t][e] is the triangle across edge e of triangle t, or None on the hull,
+/// where edge 0 is index0-index1, edge 1 is index1-index2 and edge 2 is index2-index0
+pub fn triangulate_with_neighbours(
+    points: &[Point],
+) -> Result<(Vec<Triangle>, Vec<Neighbours>), TriangulatorError> {
+    if points.len() < 3 {
+        return Err(TriangulatorError::TooFewPoints);
+    }
+
+    delaunay_inc::triangulate_with_neighbours(points)
+}
+
+/// same as triangulate, but also returns all edges, the boundary edges and the neighbours of each triangle,
+/// collected in one pass over the finished mesh
+pub fn triangulate_full(points: &[Point]) -> Result<FullTriangulation, TriangulatorError> {
+    if points.len() < 3 {
+        return Err(TriangulatorError::TooFewPoints);
+    }
+
+    delaunay_inc::triangulate_full(points)
+}
+
+/// conforming delaunay triangulation of a planar straight line graph, given as points and segments between them.
+/// segments are split at their midpoints, adding points, until no point is inside the diametral circle of any (split) segment,
+/// so that every segment is made up of edges of the triangulation
+pub fn triangulate_pslg(
+    points: &[Point],
+    segments: &[(PointIdx, PointIdx)],
+) -> Result<ConformingTriangulation, TriangulatorError> {
+    if points.len() < 3 {
+        return Err(TriangulatorError::TooFewPoints);
+    }
+
+    pslg::triangulate_pslg(points, segments)
+}
+
+/// triangulates the region inside the outer boundary and outside all holes, each given as its vertices in order.
+/// the boundaries become segments of a conforming triangulation, see triangulate_pslg, so points may be added on them.
+/// the points of the result are outer, then each hole, then the added points. gives NotSimplePolygon for a boundary without area
+pub fn triangulate_with_holes(
+    outer: &[Point],
+    holes: &[Vec<Point>],
+) -> Result<ConformingTriangulation, TriangulatorError> {
+    pslg::triangulate_with_holes(outer, holes)
+}
+
+/// triangulates a convex polygon given in counter clockwise order, without the hull and point insertion of triangulate.
+/// the vertices are added in random order, reproducibly, flipping edges so the result is delaunay.
+/// convexity isn't checked, other input gives overlapping triangles
+pub fn triangulate_convex_polygon(points: &[Point]) -> Result<Vec<Triangle>, TriangulatorError> {
+    if points.len() < 3 {
+        return Err(TriangulatorError::TooFewPoints);
+    }
+
+    delaunay_inc::triangulate_convex_polygon(points)
+}
+
+/// triangulates the inside of a simple polygon, given as its vertices in order, clockwise or counter clockwise.
+/// uses ear clipping, so unlike triangulate only the boundary vertices are used and the result isn't delaunay.
+/// the triangles are counter clockwise, or zero area at vertices collinear with their neighbours, which are kept
+/// so that none is left on the side of a triangle. gives NotSimplePolygon if it gets stuck, eg for a self
+/// intersecting polygon, or if the polygon has no area
+pub fn triangulate_polygon(boundary: &[Point]) -> Result<Vec<Triangle>, TriangulatorError> {
+    if boundary.len() < 3 {
+        return Err(TriangulatorError::TooFewPoints);
+    }
+
+    polygon::triangulate_polygon(boundary)
+}
+
+/// same as triangulate, but points with identical coordinates are collapsed into one first.
+/// the triangles index the unique points, use index_map to find their index in points
+pub fn triangulate_dedup(points: &[Point]) -> Result<Triangulation, TriangulatorError> {
+    triangulation::triangulate_dedup(points)
+}
+
+/// joins two triangulations of parts of points, eg tiles of a large data set, into one delaunay triangulation.
+/// their index_map give the index in points of the points their triangles use. the parts must share at least one
+/// boundary edge and not overlap, otherwise InvalidMesh. the gaps along the seam are filled and the seam is flipped
+/// to delaunay. the result indexes the points used by either part, in the order of points
+pub fn merge(
+    a: Triangulation,
+    b: Triangulation,
+    points: &[Point],
+) -> Result<Triangulation, TriangulatorError> {
+    delaunay_inc::merge(a, b, points)
+}
+
+/// same as triangulate_dedup, but with an attribute per point that is kept along with it.
+/// attributes[i] belongs to the point the triangles index as i, so there is no index_map to apply by hand
+pub fn triangulate_with<A: Clone>(
+    points: &[(Point, A)],
+) -> Result<(Vec<Triangle>, Vec<A>), TriangulatorError> {
+    triangulation::triangulate_with(points)
+}
+
+/// same as triangulate, but takes the points from an iterator, eg a filtered or generated one.
+/// the triangles index the points in the order the iterator gives them
+pub fn triangulate_iter<I: IntoIterator<Item = Point>>(
+    iter: I,
+) -> Result<Vec<Triangle>, TriangulatorError> {
+    let points = iter.into_iter().collect::<Vec<_>>();
+    triangulate(&points)
+}
+
+/// same as triangulate, but takes interleaved coordinates, [x0, y0, x1, y1, ...]
+pub fn triangulate_flat(coords: &[f32]) -> Result<Vec<Triangle>, TriangulatorError> {
+    if coords.len() < 6 || !coords.len().is_multiple_of(2) {
+        return Err(TriangulatorError::TooFewPoints);
+    }
+
+    triangulate(point::points_from_flat(coords))
+}
+
+/// same as triangulate_flat, but takes half precision coordinates, eg from a gpu buffer. they are widened to f32
+#[cfg(feature = "half")]
+pub fn triangulate_flat_f16(coords: &[half::f16]) -> Result<Vec<Triangle>, TriangulatorError> {
+    let coords = coords.iter().map(|c| c.to_f32()).collect::<Vec<_>>();
+    triangulate_flat(&coords)
+}
+
+/// upper bound on the number of triangles in a triangulation of num_points points, hull_size of them on the convex hull.
+/// exact unless points are collinear or coincide. 2 * num_points - 2 - hull_size, by euler's formula
+pub fn max_triangle_count(num_points: usize, hull_size: usize) -> usize {
+    (2 * num_points).saturating_sub(2 + hull_size)
+}
+
+/// with the serde feature, a partially built triangulation can be saved and restored to continue do_step
 #[derive(Default, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Triangulator {
     triangulator: DelaunayIncremental,
 }
@@ -71,6 +403,79 @@ impl Triangulator {
         }
     }
 
+    /// preallocates memory for triangulating num_points points
+    pub fn with_capacity(num_points: usize) -> Self {
+        Self {
+            triangulator: DelaunayIncremental::with_capacity(num_points),
+        }
+    }
+
+    /// creates a triangulator and does the initial triangulation, ready for do_step
+    pub fn from_points(points: &[Point]) -> Result<Self, TriangulatorError> {
+        let mut triangulator = Self::with_capacity(points.len());
+        triangulator.initial_triangulation(points)?;
+        Ok(triangulator)
+    }
+
+    /// triangulates points together with the corners of the rectangle from min to max, so that the triangles cover
+    /// all of it, eg for texture or heightmap coverage. indices 0 to 3 are the corners min, (max.x, min.y), max and
+    /// (min.x, max.y), index i + 4 is points[i], though indices in errors are into points.
+    /// points outside of the rectangle give PointOutsideOfBounds, and min above max in x or y gives InvalidBounds
+    pub fn triangulate_in_bounds(
+        points: &[Point],
+        min: Point,
+        max: Point,
+    ) -> Result<Vec<Triangle>, TriangulatorError> {
+        if !(min.x <= max.x && min.y <= max.y) {
+            return Err(TriangulatorError::InvalidBounds);
+        }
+        let mut all_points = Vec::with_capacity(points.len() + 4);
+        all_points.extend_from_slice(&[
+            min,
+            Point::new(max.x, min.y),
+            max,
+            Point::new(min.x, max.y),
+        ]);
+        for (i, p) in points.iter().enumerate() {
+            if p.x < min.x || p.x > max.x || p.y < min.y || p.y > max.y {
+                return Err(TriangulatorError::PointOutsideOfBounds(i));
+            }
+            all_points.push(*p);
+        }
+
+        // errors are for all_points, where points[i] is i + 4. the corners are neither nan nor rejected as coincident
+        triangulate(&all_points).map_err(|e| match e {
+            TriangulatorError::NANInInput(i) => TriangulatorError::NANInInput(i - 4),
+            TriangulatorError::CoincidentPoints(a, b) if a >= 4 => {
+                TriangulatorError::CoincidentPoints(a - 4, b - 4)
+            }
+            e => e,
+        })
+    }
+
+    /// starts from the given triangles instead of the convex hull, eg a saved or external triangulation,
+    /// rather than calling initial_triangulation. add points inside it with insert_point.
+    /// gives InvalidMesh for dangling indices, degenerate or duplicate triangles, edges shared by more than two triangles
+    /// or inverted triangles, on the same side of an edge as their neighbour. the triangles may have either winding
+    pub fn from_existing(
+        triangles: Vec<Triangle>,
+        points: &[Point],
+    ) -> Result<Self, TriangulatorError> {
+        Ok(Self {
+            triangulator: DelaunayIncremental::from_existing(triangles, points)?,
+        })
+    }
+
+    /// adds points[point_idx], which must be inside the current triangles. can be undone with undo_step.
+    /// gives PointIndexOutOfRange if point_idx isn't an index into points
+    pub fn insert_point(
+        &mut self,
+        point_idx: PointIdx,
+        points: &[Point],
+    ) -> Result<(), TriangulatorError> {
+        self.triangulator.insert_point(point_idx, points)
+    }
+
     pub fn initial_triangulation(
         &mut self,
         points: &[Point],
@@ -82,18 +487,431 @@ impl Triangulator {
         self.triangulator.initial_triangulation(points)
     }
 
+    pub fn num_triangles(&self) -> usize {
+        self.get_triangles().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.get_triangles().is_empty()
+    }
+
+    /// number of points left to add, do_step returns false when this is zero
+    pub fn points_remaining(&self) -> usize {
+        self.triangulator.points_remaining()
+    }
+
+    /// circumcircle of each triangle, in triangle order. degenerate (collinear) triangles give non-finite circles
+    pub fn circumcircles(&self, points: &[Point]) -> Vec<Circle> {
+        self.get_triangles()
+            .iter()
+            .map(|tri| Circle::from_triangle(tri, points))
+            .collect()
+    }
+
+    /// center of the circumcircle of triangle tri, without its radius.
+    /// non-finite for a degenerate (collinear) triangle. panics if tri is out of range
+    pub fn circumcenter(&self, points: &[Point], tri: TriIdx) -> Point {
+        let tri = &self.get_triangles()[tri as usize];
+        circle::circumcenter(
+            &points[tri.index0 as usize],
+            &points[tri.index1 as usize],
+            &points[tri.index2 as usize],
+        )
+    }
+
+    /// area of the voronoi cell of each point, in point order. None for hull points, whose cells are unbounded,
+    /// and for points not in the triangulation
+    pub fn voronoi_cell_areas(&self, points: &[Point]) -> Vec<Option<f32>> {
+        let (_, on_boundary) = self.triangulator.vertex_adjacency(points.len());
+        voronoi::cell_corners(points, self.get_triangles())
+            .iter_mut()
+            .enumerate()
+            .map(|(idx, corners)| {
+                if on_boundary[idx] || corners.len() < 3 {
+                    None
+                } else {
+                    Some(voronoi::cell_area(&points[idx], corners))
+                }
+            })
+            .collect()
+    }
+
+    /// area of each triangle, in triangle order
+    pub fn triangle_areas<'a>(&'a self, points: &'a [Point]) -> impl Iterator<Item = f32> + 'a {
+        self.get_triangles().iter().map(move |tri| tri.area(points))
+    }
+
+    /// sum of all triangle areas, which equals the area of the convex hull
+    pub fn total_area(&self, points: &[Point]) -> f32 {
+        // accumulate in f64 to limit error on large meshes
+        self.triangle_areas(points).map(f64::from).sum::<f64>() as f32
+    }
+
+    /// (min, max) corners of the box around the points used by the triangles, None if there are none.
+    /// can be smaller than the box around all points, if some aren't added yet
+    pub fn bounding_box(&self, points: &[Point]) -> Option<(Point, Point)> {
+        triangle::bounding_box(self.get_triangles(), points)
+    }
+
+    /// checks the mesh invariants: no degenerate or duplicate triangles, no edge shared by more than two triangles,
+    /// and the internal edge mapping matches the triangles
+    pub fn validate(&self, points: &[Point]) -> Result<(), Vec<ValidationIssue>> {
+        let issues = self.triangulator.validate(points);
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(issues)
+        }
+    }
+
+    /// points that were skipped since they coincide with an already added vertex, as (point, vertex)
+    pub fn merged_points(&self) -> &[(PointIdx, PointIdx)] {
+        &self.triangulator.merged
+    }
+
+    /// true if p is inside, or on the boundary of, any triangle.
+    /// for a finished delaunay triangulation, that is the same as inside the convex hull of points
+    pub fn contains_point(&self, points: &[Point], p: &Point) -> bool {
+        interpolation::locate(self.get_triangles(), points, p).is_some()
+    }
+
+    /// the k vertices closest to query, closest first.
+    /// found by walking outwards from the triangle containing query through the triangle edges, stopping after k vertices,
+    /// so only the visited part of the mesh is sorted. exact inside the triangulation,
+    /// but approximate near and outside the hull, where the walk starts from the closest triangle centroid instead
+    pub fn k_nearest(&self, points: &[Point], query: &Point, k: usize) -> Vec<PointIdx> {
+        let triangles = self.get_triangles();
+        let start = match interpolation::locate(triangles, points, query) {
+            Some((tri_idx, _)) => tri_idx,
+            None => {
+                let dist_sqr = |tri: &Triangle| {
+                    let d = tri.centroid(points) - query;
+                    d.x * d.x + d.y * d.y
+                };
+                match (0..triangles.len()).min_by(|a, b| {
+                    dist_sqr(&triangles[*a])
+                        .partial_cmp(&dist_sqr(&triangles[*b]))
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                }) {
+                    Some(tri_idx) => tri_idx,
+                    None => return Vec::new(),
+                }
+            }
+        };
+
+        self.triangulator
+            .k_nearest(points, query, start as TriIdx, k)
+    }
+
+    /// linearly interpolates values (one per point) at query, using the barycentric coordinates of the triangle containing query.
+    /// None if query is outside of the triangulation or values and points differ in length
+    pub fn interpolate(&self, points: &[Point], values: &[f32], query: &Point) -> Option<f32> {
+        interpolation::linear(self.get_triangles(), points, values, query)
+    }
+
+    /// natural neighbour (Sibson) interpolation of values (one per point) at query. smoother, but more expensive, than interpolate.
+    /// None if query is outside of the triangulation or values and points differ in length
+    pub fn natural_neighbour_interpolate(
+        &self,
+        points: &[Point],
+        values: &[f32],
+        query: &Point,
+    ) -> Option<f32> {
+        interpolation::natural_neighbour(self.get_triangles(), points, values, query)
+    }
+
+    /// for each triangle, the triangles sharing an edge with it, in ascending order
+    pub fn dual_graph(&self) -> Vec<Vec<TriIdx>> {
+        self.triangulator.dual_graph()
+    }
+
+    /// neighbours[t][e] is the triangle across edge e of triangle t, see triangulate_with_neighbours
+    pub fn neighbours(&self) -> Vec<Neighbours> {
+        self.triangulator.neighbours()
+    }
+
+    /// replaces edge with the other diagonal of the quad formed by the two triangles sharing it.
+    /// the result is not necessarily delaunay
+    pub fn flip_edge(&mut self, edge: Edge, points: &[Point]) -> Result<(), FlipError> {
+        self.triangulator.flip_edge(&edge, points)
+    }
+
+    /// merges the two end points of edge into their midpoint, removing the triangles sharing the edge.
+    /// the midpoint replaces points[edge.index_0 as usize], while edge.index_1 is no longer used by any triangle.
+    /// rejected if it would invert a triangle. the result is not necessarily delaunay
+    pub fn collapse_edge(&mut self, edge: Edge, points: &mut [Point]) -> Result<(), CollapseError> {
+        self.triangulator.collapse_edge(&edge, points)
+    }
+
+    /// moves points[vertex] to new_pos and restores the delaunay property, eg while dragging it.
+    /// a small move, keeping the orientation of the triangles around vertex, only flips edges near it.
+    /// otherwise vertex is removed and inserted again, and if new_pos is outside of the triangles that gives
+    /// PointOutsideOfHull with nothing changed, not even points. vertex must also be an interior point, not on the
+    /// hull, else PointOutsideOfHull with nothing changed. points not in the triangulation only get their new position
+    pub fn move_vertex(
+        &mut self,
+        points: &mut [Point],
+        vertex: PointIdx,
+        new_pos: Point,
+    ) -> Result<(), TriangulatorError> {
+        self.triangulator.move_vertex(points, vertex, new_pos)
+    }
+
+    /// removes interior points until target_count points are left, re-triangulating each hole, for a coarser mesh of
+    /// the same domain. points closest to a neighbour go first. boundary points are kept, so fewer than their number
+    /// can't be reached. afterwards points only holds the points still used, in their previous order, and the
+    /// triangles index them. intended for finished triangulations
+    pub fn decimate(&mut self, points: &mut Vec<Point>, target_count: usize) {
+        decimate::decimate(&mut self.triangulator, points, None, target_count);
+    }
+
+    /// like decimate, but removes the points whose removal changes the interpolated height the least first,
+    /// eg for terrain. heights are per point and are compacted along with points. panics if their lengths differ
+    pub fn decimate_with_heights(
+        &mut self,
+        points: &mut Vec<Point>,
+        heights: &mut Vec<f32>,
+        target_count: usize,
+    ) {
+        assert_eq!(points.len(), heights.len(), "one height per point");
+        decimate::decimate(&mut self.triangulator, points, Some(heights), target_count);
+    }
+
+    /// removes triangles whose centroid is outside of the polygon given by boundary, indices into points.
+    /// triangles straddling the boundary are thus kept or removed depending on where their centroid is.
+    /// holes can be cut by clipping again with the hole's outline, since being outside of a polygon is what's tested.
+    /// intended for finished triangulations, do_step should not be called after clipping
+    pub fn clip_to_polygon(&mut self, points: &[Point], boundary: &[PointIdx]) {
+        self.triangulator.retain_triangles(|tri| {
+            polygon::point_in_polygon(&tri.centroid(points), boundary, points)
+        });
+    }
+
+    /// moves each vertex towards the average of its neighbours, iterations times.
+    /// boundary vertices are left in place if boundary_fixed is set.
+    /// the moved points are generally no longer delaunay triangulated by this mesh, re-triangulate them if that is needed
+    pub fn smooth_laplacian(&self, points: &mut [Point], iterations: usize, boundary_fixed: bool) {
+        let (neighbours, on_boundary) = self.triangulator.vertex_adjacency(points.len());
+
+        for _ in 0..iterations {
+            let smoothed = neighbours
+                .iter()
+                .enumerate()
+                .map(|(idx, ring)| {
+                    if ring.is_empty() || (boundary_fixed && on_boundary[idx]) {
+                        points[idx]
+                    } else {
+                        let sum = ring
+                            .iter()
+                            .fold(Point::new(0.0, 0.0), |sum, n| sum + &points[*n as usize]);
+                        sum * (1.0 / ring.len() as f32)
+                    }
+                })
+                .collect::<Vec<_>>();
+            points.copy_from_slice(&smoothed);
+        }
+    }
+
+    /// indices of triangles with an interior angle smaller than min_angle_deg degrees
+    pub fn slivers(&self, points: &[Point], min_angle_deg: f32) -> Vec<TriIdx> {
+        let min_angle = min_angle_deg.to_radians();
+        self.get_triangles()
+            .iter()
+            .enumerate()
+            .filter(|(_, tri)| tri.min_angle(points) < min_angle)
+            .map(|(idx, _)| idx as TriIdx)
+            .collect()
+    }
+
+    /// indices of triangles with an area below epsilon, from collinear or coincident points.
+    /// their circumcircles are infinite or undefined
+    pub fn degenerate_triangles(&self, points: &[Point], epsilon: f32) -> Vec<TriIdx> {
+        self.get_triangles()
+            .iter()
+            .enumerate()
+            .filter(|(_, tri)| tri.area(points) < epsilon)
+            .map(|(idx, _)| idx as TriIdx)
+            .collect()
+    }
+
+    /// the vertices sharing an edge with vertex, in ascending order
+    pub fn one_ring(&self, vertex: PointIdx) -> Vec<PointIdx> {
+        self.triangulator.one_ring(vertex)
+    }
+
+    /// merges the triangles into fewer, larger convex polygons, eg for navigation meshes.
+    /// greedy (hertel-mehlhorn): an edge between two polygons is removed if the merged polygon is convex,
+    /// which gives at most four times the minimum number of polygons. polygons are counter clockwise
+    pub fn convex_partition(&self, points: &[Point]) -> Vec<Vec<PointIdx>> {
+        self.triangulator.convex_partition(points)
+    }
+
+    /// the triangles using vertex, in ascending order
+    pub fn triangles_around(&self, vertex: PointIdx) -> Vec<TriIdx> {
+        self.triangulator.triangles_around(vertex)
+    }
+
+    /// the triangles sharing a vertex with triangle tri, not only those sharing an edge, in ascending order.
+    /// tri itself is not included. panics if tri is out of range
+    pub fn triangle_vertex_neighbours(&self, tri: TriIdx) -> Vec<TriIdx> {
+        self.triangulator.triangle_vertex_neighbours(tri)
+    }
+
+    /// the triangles with at least one edge on the boundary, in ascending order, eg for boundary conditions
+    pub fn boundary_triangles(&self) -> Vec<TriIdx> {
+        let mut tris = boundary_edges(self.get_triangles())
+            .into_iter()
+            .flat_map(|(a, b)| self.triangulator.triangles_of_edge(&Edge::new(a, b)))
+            .collect::<Vec<_>>();
+        tris.sort_unstable();
+        tris.dedup();
+        tris
+    }
+
+    /// the triangles having edge as one of their edges, in ascending order.
+    /// one on the boundary, two inside, none if edge isn't in the triangulation
+    pub fn triangles_of_edge(&self, edge: Edge) -> Vec<TriIdx> {
+        self.triangulator.triangles_of_edge(&edge)
+    }
+
+    /// vertex, edge, triangle and component counts and the euler characteristic, a cheap check for corruption
+    pub fn topology(&self) -> Topology {
+        self.triangulator.topology()
+    }
+
+    /// the (shortest, longest) edge of the triangulation, eg to see how anisotropic it is. None without triangles
+    pub fn edge_length_extremes(&self, points: &[Point]) -> Option<(Edge, Edge)> {
+        self.triangulator.edge_length_extremes(points)
+    }
+
+    /// interior edges whose triangles would be flipped by the delaunay criterion, sorted.
+    /// empty for a finished delaunay triangulation, so it points out where one isn't
+    pub fn non_delaunay_edges(&self, points: &[Point]) -> Vec<Edge> {
+        self.triangulator.non_delaunay_edges(points)
+    }
+
+    /// the other triangle having edge, across it from triangle tri.
+    /// None if edge is on the boundary or isn't an edge of tri
+    pub fn opposite_triangle(&self, tri: TriIdx, edge: Edge) -> Option<TriIdx> {
+        self.triangulator.opposite_triangle(tri, &edge)
+    }
+
+    /// true if edge is used by exactly one triangle, false for interior edges and edges not in the triangulation
+    pub fn is_boundary_edge(&self, edge: Edge) -> bool {
+        self.triangulator.is_boundary_edge(&edge)
+    }
+
+    /// sets the order interior points are inserted in, used by the next initial_triangulation
+    pub fn set_insertion_order(&mut self, order: InsertionOrder) {
+        self.triangulator.insertion_order = order;
+    }
+
+    /// tolerances of the geometric tests, used from the next inserted point
+    pub fn set_options(&mut self, options: TriangulatorOptions) {
+        self.triangulator.options = options;
+    }
+
+    pub fn options(&self) -> TriangulatorOptions {
+        self.triangulator.options
+    }
+
+    /// clears the triangulation but keeps allocated memory, for reuse with another initial_triangulation
+    pub fn reset(&mut self) {
+        self.triangulator.reset();
+    }
+
+    /// discards all inserted points, going back to the triangulated hull from initial_triangulation with the same
+    /// points left to add, without computing the hull again. points must be the same as for initial_triangulation.
+    /// false, with nothing changed, if there was no initial_triangulation, eg after from_existing
+    pub fn reset_to_hull(&mut self, points: &[Point]) -> bool {
+        self.triangulator.reset_to_hull(points)
+    }
+
     pub fn do_step(&mut self, points: &[Point]) -> bool {
         self.triangulator.do_step(points)
     }
 
+    /// true if flipping gave up after TriangulatorOptions::max_flips_per_triangle during initial_triangulation
+    /// or a do_step since, where add_all would give FlipLimitExceeded. the triangles are valid, but maybe not delaunay
+    pub fn flip_limit_exceeded(&self) -> bool {
+        self.triangulator.flip_limit_exceeded()
+    }
+
+    /// reverts the most recent do_step, so that its point is added again by the next do_step.
+    /// can be repeated back to the initial triangulation. returns false if there is no step to undo.
+    /// flip_edge and clip_to_polygon can't be undone, and forget the earlier steps
+    pub fn undo_step(&mut self) -> bool {
+        self.triangulator.undo_step()
+    }
+
+    /// runs the whole triangulation, calling observer after each inserted point with the current triangles and the index of the point just added
+    pub fn triangulate_with_observer(
+        &mut self,
+        points: &[Point],
+        observer: &mut dyn FnMut(&[Triangle], PointIdx),
+    ) -> Result<&[Triangle], TriangulatorError> {
+        self.initial_triangulation(points)?;
+
+        while let Some(point_idx) = self.triangulator.step(points) {
+            observer(self.triangulator.get_triangles(), point_idx);
+        }
+
+        Ok(self.get_triangles())
+    }
+
     pub fn get_triangles(&self) -> &[Triangle] {
         self.triangulator.get_triangles()
     }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Triangle> {
+        self.get_triangles().iter()
+    }
+
+    /// the triangles, without cloning them
+    pub fn into_triangles(self) -> Vec<Triangle> {
+        self.triangulator.triangles
+    }
+}
+
+// a status line, Debug has the details
+impl std::fmt::Display for Triangulator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Triangulator {{ triangles: {}, points_remaining: {} }}",
+            self.num_triangles(),
+            self.points_remaining()
+        )
+    }
+}
+
+impl<'a> IntoIterator for &'a Triangulator {
+    type Item = &'a Triangle;
+    type IntoIter = std::slice::Iter<'a, Triangle>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for Triangulator {
+    type Item = Triangle;
+    type IntoIter = std::vec::IntoIter<Triangle>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_triangles().into_iter()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{triangulate, Point, Triangle, TriangulatorError};
+    use crate::{
+        boundary_edges, boundary_polygon, max_triangle_count, merge, triangulate,
+        triangulate_convex_polygon, triangulate_flat, triangulate_full, triangulate_i32,
+        triangulate_into, triangulate_iter, triangulate_weighted, triangulate_with_neighbours,
+        Circle, CollapseError, Edge, FlipError, InsertionOrder, Point, PointIdx, Topology, TriIdx,
+        Triangle, Triangulation, Triangulator, TriangulatorError, ValidationIssue,
+    };
 
     #[test]
     fn returns_too_few_points() {
@@ -131,6 +949,32 @@ mod tests {
         assert_eq!(res.unwrap().len(), 1);
     }
 
+    #[test]
+    fn collinear_points_give_error() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(3.0, 3.0),
+        ];
+        assert_eq!(triangulate(&points), Err(TriangulatorError::Collinear));
+
+        let vertical = [
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 3.0),
+            Point::new(2.0, 1.0),
+            Point::new(2.0, 3.0),
+        ];
+        assert_eq!(triangulate(&vertical), Err(TriangulatorError::Collinear));
+        assert_eq!(
+            triangulate_i32(&[(0, 0), (1, 2), (2, 4)]),
+            Err(TriangulatorError::Collinear)
+        );
+        assert_eq!(
+            triangulate(&[Point::new(1.0, 1.0); 4]),
+            Err(TriangulatorError::Collinear)
+        );
+    }
+
     #[test]
     fn returns_three_triangles() {
         let points = [
@@ -161,10 +1005,48 @@ mod tests {
     }
 
     #[test]
-    fn avoids_obtuse_triangles() {
-        // result should be:
-        //   /\
-        //  /__\
+    fn identical_values_are_order_independent() {
+        use itertools::Itertools;
+
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.5, 1.0),
+            Point::new(0.5, 0.4),
+            Point::new(0.5, 0.4),
+            Point::new(1.0, 0.0),
+        ];
+        // triangles by coordinates, since coincident points are interchangeable
+        let as_coords = |triangles: &[Triangle], points: &[Point]| {
+            let mut coords = triangles
+                .iter()
+                .map(|tri| {
+                    let mut corners = [tri.index0, tri.index1, tri.index2].map(|i| {
+                        (
+                            points[i as usize].x.to_bits(),
+                            points[i as usize].y.to_bits(),
+                        )
+                    });
+                    corners.sort_unstable();
+                    corners
+                })
+                .collect::<Vec<_>>();
+            coords.sort_unstable();
+            coords
+        };
+        let expected = as_coords(&triangulate(&points).unwrap(), &points);
+
+        for permutation in points.iter().cloned().permutations(points.len()) {
+            let triangles = triangulate(&permutation).unwrap();
+            assert_eq!(as_coords(&triangles, &permutation), expected);
+        }
+    }
+
+    #[test]
+    fn avoids_obtuse_triangles() {
+        // result should be:
+        //   /\
+        //  /__\
         //  \  /
         //   \/
         // and not:
@@ -209,27 +1091,1591 @@ mod tests {
     }
 
     #[test]
-    fn offending_points() {
-        use rand::seq::SliceRandom;
+    fn flat_returns_too_few_points() {
+        let coords = [0.0, 0.0, 1.0, 0.0, 0.5];
 
-        // these are offending combinations of points, caused triangulate to fail:
-        let fails = [
-            Point::new(22., 20.),
-            Point::new(141., 20.),
-            Point::new(245., 169.),
-            Point::new(268., 134.),
-            Point::new(314., 133.),
-            Point::new(69., 20.),
+        let res = triangulate_flat(&coords);
+
+        assert_eq!(res, Err(TriangulatorError::TooFewPoints));
+    }
+
+    #[test]
+    fn flat_same_as_points() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(0.0, 1.0),
+            Point::new(0.5, 0.5),
         ];
+        let coords = [0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0, 0.5, 0.5];
 
-        for _ in 0..720 {
-            let subset: Vec<Point> = fails
-                .choose_multiple(&mut rand::thread_rng(), 6)
-                .map(|x| *x)
-                .collect();
+        let expected = triangulate(&points).unwrap();
+        let triangles = triangulate_flat(&coords).unwrap();
 
-            let triangles = triangulate(&subset);
-            assert!(triangles.is_ok());
+        assert_eq!(triangles.len(), expected.len());
+        for tri in triangles.iter() {
+            assert!(expected.iter().any(|e| e.equivalent(tri)));
+        }
+    }
+
+    #[cfg(feature = "half")]
+    #[test]
+    fn flat_f16_same_as_f32() {
+        use crate::triangulate_flat_f16;
+        use half::f16;
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(2);
+        let coords = (0..60)
+            .map(|_| f16::from_f32(rng.gen_range(-100.0..100.0)))
+            .collect::<Vec<_>>();
+        let widened = coords.iter().map(|c| c.to_f32()).collect::<Vec<_>>();
+
+        assert_eq!(
+            triangulate_flat_f16(&coords).unwrap(),
+            triangulate_flat(&widened).unwrap()
+        );
+        assert_eq!(
+            triangulate_flat_f16(&coords[..5]),
+            Err(TriangulatorError::TooFewPoints)
+        );
+    }
+
+    #[test]
+    fn iter_same_as_slice() {
+        let points = (0..40)
+            .map(|i| {
+                let i = i as f32;
+                Point::new((i * 2.3).sin() * i, (i * 1.7).cos() * i)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            triangulate_iter(points.iter().copied()).unwrap(),
+            triangulate(&points).unwrap()
+        );
+        assert_eq!(
+            triangulate_iter(std::iter::empty()),
+            Err(TriangulatorError::TooFewPoints)
+        );
+    }
+
+    #[test]
+    fn reset_keeps_capacity() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(0.0, 1.0),
+            Point::new(0.5, 0.5),
+        ];
+
+        let mut triangulator = Triangulator::new();
+        triangulator.initial_triangulation(&points).unwrap();
+        while triangulator.do_step(&points) {}
+        let capacity = triangulator.triangulator.triangles.capacity();
+
+        triangulator.reset();
+
+        assert!(triangulator.get_triangles().is_empty());
+        assert_eq!(triangulator.triangulator.triangles.capacity(), capacity);
+    }
+
+    #[test]
+    fn triangulate_into_reuses_buffer() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.5, 1.0),
+            Point::new(0.5, 0.5),
+        ];
+        let mut out = Vec::with_capacity(64);
+        out.push(Triangle::new(7, 8, 9));
+        let ptr = out.as_ptr();
+
+        triangulate_into(&points, &mut out).unwrap();
+
+        assert_eq!(out.len(), 3);
+        assert_eq!(out.capacity(), 64);
+        assert_eq!(out.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn observer_called_once_per_interior_point() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(2.0, 4.0),
+            Point::new(2.0, 1.0),
+            Point::new(1.5, 1.5),
+            Point::new(2.5, 1.5),
+        ];
+
+        let mut added = Vec::new();
+        let mut triangulator = Triangulator::new();
+        let triangles = triangulator
+            .triangulate_with_observer(&points, &mut |triangles, point_idx| {
+                assert!(!triangles.is_empty());
+                added.push(point_idx);
+            })
+            .unwrap();
+        assert_eq!(triangles.len(), 7);
+
+        added.sort_unstable();
+        assert_eq!(added, vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn points_remaining_counts_down() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(2.0, 4.0),
+            Point::new(2.0, 1.0),
+            Point::new(1.5, 1.5),
+        ];
+
+        let mut triangulator = Triangulator::new();
+        assert!(triangulator.is_empty());
+
+        triangulator.initial_triangulation(&points).unwrap();
+        assert_eq!(triangulator.num_triangles(), 1);
+        assert_eq!(triangulator.points_remaining(), 2);
+
+        assert!(triangulator.do_step(&points));
+        assert_eq!(triangulator.points_remaining(), 1);
+
+        assert!(triangulator.do_step(&points));
+        assert_eq!(triangulator.points_remaining(), 0);
+
+        assert!(!triangulator.do_step(&points));
+        assert_eq!(triangulator.num_triangles(), 5);
+        assert!(!triangulator.is_empty());
+    }
+
+    #[test]
+    fn circumcircle_passes_through_vertices() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(3.0, 0.0),
+            Point::new(1.0, 2.0),
+        ];
+
+        let mut triangulator = Triangulator::new();
+        triangulator.initial_triangulation(&points).unwrap();
+        let circles: Vec<Circle> = triangulator.circumcircles(&points);
+
+        assert_eq!(circles.len(), 1);
+        for p in points.iter() {
+            let dx = p.x - circles[0].pos.x;
+            let dy = p.y - circles[0].pos.y;
+            assert!((dx * dx + dy * dy - circles[0].radius_sqr).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn total_area_of_square() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+            Point::new(0.5, 1.5),
+            Point::new(1.2, 0.7),
+        ];
+
+        let mut triangulator = Triangulator::new();
+        triangulator.initial_triangulation(&points).unwrap();
+        while triangulator.do_step(&points) {}
+
+        assert_eq!(triangulator.triangle_areas(&points).count(), 6);
+        assert!(triangulator.triangle_areas(&points).all(|a| a > 0.0));
+        assert!((triangulator.total_area(&points) - 4.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn with_capacity_triangulates() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.5, 1.0),
+            Point::new(0.5, 0.5),
+        ];
+
+        let mut triangulator = Triangulator::with_capacity(points.len());
+        assert!(triangulator.triangulator.triangles.capacity() >= 3);
+        triangulator.initial_triangulation(&points).unwrap();
+        while triangulator.do_step(&points) {}
+
+        assert_eq!(triangulator.num_triangles(), 3);
+    }
+
+    #[test]
+    fn from_points_triangulates() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.5, 1.0),
+            Point::new(0.5, 0.5),
+        ];
+
+        let mut triangulator = Triangulator::from_points(&points).unwrap();
+        assert_eq!(triangulator.points_remaining(), 1);
+        while triangulator.do_step(&points) {}
+
+        assert_eq!(triangulator.num_triangles(), 3);
+        assert_eq!(
+            Triangulator::from_points(&points[..2]).err(),
+            Some(TriangulatorError::TooFewPoints)
+        );
+    }
+
+    #[test]
+    fn dual_graph_is_symmetric() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+            Point::new(0.5, 1.5),
+            Point::new(1.2, 0.7),
+        ];
+
+        let mut triangulator = Triangulator::from_points(&points).unwrap();
+        while triangulator.do_step(&points) {}
+        let graph = triangulator.dual_graph();
+
+        assert_eq!(graph.len(), triangulator.num_triangles());
+        for (tri, neighbours) in graph.iter().enumerate() {
+            assert!(!neighbours.is_empty() && neighbours.len() <= 3);
+            for neighbour in neighbours {
+                assert!(graph[*neighbour as usize].contains(&(tri as TriIdx)));
+            }
+        }
+    }
+
+    #[test]
+    fn convex_polygon_has_n_minus_2_triangles() {
+        for n in 3..12 {
+            let points = (0..n)
+                .map(|i| {
+                    let angle = i as f32 * std::f32::consts::TAU / n as f32;
+                    Point::new(angle.cos(), angle.sin())
+                })
+                .collect::<Vec<_>>();
+
+            let triangles = triangulate_convex_polygon(&points).unwrap();
+
+            assert_eq!(triangles.len(), n - 2);
+            assert!(triangles.iter().all(|t| t.area(&points) > 0.0));
+        }
+        assert_eq!(
+            triangulate_convex_polygon(&[Point::new(0.0, 0.0), Point::new(1.0, 0.0)]),
+            Err(TriangulatorError::TooFewPoints)
+        );
+    }
+
+    #[test]
+    fn k_nearest_all_points_sorted() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(8);
+        let points = (0..40)
+            .map(|_| Point::new(rng.gen_range(0.0..10.0), rng.gen_range(0.0..10.0)))
+            .collect::<Vec<_>>();
+        let mut triangulator = Triangulator::from_points(&points).unwrap();
+        while triangulator.do_step(&points) {}
+        let dist_sqr = |idx: &PointIdx, q: &Point| {
+            let d = points[*idx as usize] - q;
+            d.x * d.x + d.y * d.y
+        };
+
+        for query in [Point::new(4.2, 5.1), Point::new(-3.0, 12.0)] {
+            let nearest = triangulator.k_nearest(&points, &query, points.len());
+
+            let mut expected = (0..points.len() as PointIdx).collect::<Vec<_>>();
+            expected.sort_by(|a, b| {
+                dist_sqr(a, &query)
+                    .partial_cmp(&dist_sqr(b, &query))
+                    .unwrap()
+            });
+            assert_eq!(nearest, expected);
+        }
+
+        let query = Point::new(4.2, 5.1);
+        let three = triangulator.k_nearest(&points, &query, 3);
+        let all = triangulator.k_nearest(&points, &query, points.len());
+        assert_eq!(three, all[..3]);
+    }
+
+    #[test]
+    fn convex_partition_covers_mesh() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(9);
+        let points = (0..50)
+            .map(|_| Point::new(rng.gen_range(0.0..10.0), rng.gen_range(0.0..10.0)))
+            .collect::<Vec<_>>();
+        let mut triangulator = Triangulator::from_points(&points).unwrap();
+        while triangulator.do_step(&points) {}
+
+        let polygons = triangulator.convex_partition(&points);
+
+        assert!(polygons.len() < triangulator.num_triangles());
+        let mut area = 0.0;
+        for polygon in polygons.iter() {
+            let n = polygon.len();
+            assert!(n >= 3);
+            for i in 0..n {
+                let [a, b, c] = [i, (i + 1) % n, (i + 2) % n].map(|j| points[polygon[j] as usize]);
+                assert!((b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y) >= 0.0);
+            }
+            // fan area, valid for convex polygons
+            for i in 1..n - 1 {
+                area += Triangle::new(polygon[0], polygon[i], polygon[i + 1]).area(&points);
+            }
+        }
+        let expected = triangulator.total_area(&points);
+        assert!((area - expected).abs() < 1e-3 * expected);
+    }
+
+    #[test]
+    fn neighbours_are_symmetric_on_fan() {
+        // hexagon around a center point, 6 triangles in a fan
+        let mut points = (0..6)
+            .map(|i| {
+                let angle = i as f32 * std::f32::consts::PI / 3.0;
+                Point::new(angle.cos(), angle.sin())
+            })
+            .collect::<Vec<_>>();
+        points.push(Point::new(0.0, 0.0));
+
+        let (triangles, neighbours) = triangulate_with_neighbours(&points).unwrap();
+        let edge = |tri: &Triangle, e: usize| {
+            let idx = [tri.index0, tri.index1, tri.index2];
+            Edge::new(idx[e], idx[(e + 1) % 3])
+        };
+
+        assert_eq!(triangles.len(), 6);
+        assert_eq!(neighbours.len(), 6);
+        let mut hull_edges = 0;
+        for (t, across) in neighbours.iter().enumerate() {
+            for (e, neighbour) in across.iter().enumerate() {
+                let shared = edge(&triangles[t], e);
+                match neighbour {
+                    Some(n) => {
+                        let back = neighbours[*n as usize]
+                            .iter()
+                            .position(|other| *other == Some(t as TriIdx))
+                            .unwrap();
+                        assert_eq!(edge(&triangles[*n as usize], back), shared);
+                    }
+                    None => hull_edges += 1,
+                }
+            }
+        }
+        assert_eq!(hull_edges, 6);
+    }
+
+    #[test]
+    fn flip_edge_twice_restores() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(1.2, 1.0),
+            Point::new(0.0, 1.0),
+        ];
+
+        let mut triangulator = Triangulator::from_points(&points).unwrap();
+        let original = triangulator.get_triangles().to_vec();
+        let diagonal = if original[0].equivalent(&Triangle::new(0, 1, 2))
+            || original[0].equivalent(&Triangle::new(0, 2, 3))
+        {
+            (Edge::new(0, 2), Edge::new(1, 3))
+        } else {
+            (Edge::new(1, 3), Edge::new(0, 2))
+        };
+
+        triangulator.flip_edge(diagonal.0, &points).unwrap();
+        assert_eq!(
+            triangulator.flip_edge(diagonal.0, &points),
+            Err(FlipError::UnknownEdge)
+        );
+        triangulator.flip_edge(diagonal.1, &points).unwrap();
+
+        for (tri, original) in triangulator.get_triangles().iter().zip(original.iter()) {
+            assert!(tri.equivalent(original));
+        }
+    }
+
+    #[test]
+    fn flip_edge_rejects_boundary_and_non_convex() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.5, 1.0),
+            Point::new(0.5, 0.5),
+        ];
+
+        let mut triangulator = Triangulator::from_points(&points).unwrap();
+        while triangulator.do_step(&points) {}
+
+        assert_eq!(
+            triangulator.flip_edge(Edge::new(0, 1), &points),
+            Err(FlipError::BoundaryEdge)
+        );
+        assert_eq!(
+            triangulator.flip_edge(Edge::new(0, 3), &points),
+            Err(FlipError::NonConvexQuad)
+        );
+    }
+
+    #[test]
+    fn clip_to_l_shape() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 1.0),
+            Point::new(1.0, 1.0),
+            Point::new(1.0, 2.0),
+            Point::new(0.0, 2.0),
+            Point::new(2.0, 2.0),
+        ];
+
+        let mut triangulator = Triangulator::from_points(&points).unwrap();
+        while triangulator.do_step(&points) {}
+        assert!((triangulator.total_area(&points) - 4.0).abs() < 1e-5);
+
+        triangulator.clip_to_polygon(&points, &[0, 1, 2, 3, 4, 5]);
+
+        assert!((triangulator.total_area(&points) - 3.0).abs() < 1e-5);
+        assert!(triangulator
+            .get_triangles()
+            .iter()
+            .all(|tri| { tri.index0 != 6 && tri.index1 != 6 && tri.index2 != 6 }));
+        for (tri, neighbours) in triangulator.dual_graph().iter().enumerate() {
+            for neighbour in neighbours {
+                assert!(
+                    (*neighbour as usize) < triangulator.num_triangles()
+                        && *neighbour as usize != tri
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn smooth_laplacian_centers_vertex() {
+        let mut points = vec![Point::new(0.3, -0.2)];
+        for i in 0..6 {
+            let angle = i as f32 * std::f32::consts::PI / 3.0;
+            points.push(Point::new(angle.cos(), angle.sin()));
+        }
+
+        let mut triangulator = Triangulator::from_points(&points).unwrap();
+        while triangulator.do_step(&points) {}
+        let rim = points[1..].to_vec();
+
+        triangulator.smooth_laplacian(&mut points, 3, true);
+
+        assert!(points[0].x.abs() < 1e-5 && points[0].y.abs() < 1e-5);
+        for (p, r) in points[1..].iter().zip(rim.iter()) {
+            assert_eq!((p.x, p.y), (r.x, r.y));
+        }
+    }
+
+    #[test]
+    fn slivers_flags_thin_triangle() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(1.0, 1.7),
+            Point::new(1.0, 0.05),
+        ];
+
+        let mut triangulator = Triangulator::from_points(&points).unwrap();
+        while triangulator.do_step(&points) {}
+
+        let slivers = triangulator.slivers(&points, 10.0);
+
+        assert_eq!(slivers.len(), 1);
+        let sliver = &triangulator.get_triangles()[slivers[0] as usize];
+        assert!(sliver.equivalent(&Triangle::new(0, 1, 3)));
+        assert!(triangulator.slivers(&points, 1.0).is_empty());
+    }
+
+    #[test]
+    fn degenerate_triangles_flags_collinear_triangle() {
+        let mut points = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(1.0, 1.7),
+            Point::new(1.0, 0.05),
+        ];
+
+        let mut triangulator = Triangulator::from_points(&points).unwrap();
+        while triangulator.do_step(&points) {}
+        assert!(triangulator.degenerate_triangles(&points, 1e-6).is_empty());
+
+        // moved onto the edge between 0 and 1
+        points[3] = Point::new(1.0, 0.0);
+        let degenerate = triangulator.degenerate_triangles(&points, 1e-6);
+
+        assert_eq!(degenerate.len(), 1);
+        let tri = &triangulator.get_triangles()[degenerate[0] as usize];
+        assert!(tri.equivalent(&Triangle::new(0, 1, 3)));
+    }
+
+    #[test]
+    fn one_ring_of_fan_center() {
+        let mut points = vec![Point::new(0.1, 0.0)];
+        for i in 0..6 {
+            let angle = i as f32 * std::f32::consts::PI / 3.0;
+            points.push(Point::new(angle.cos(), angle.sin()));
+        }
+
+        let mut triangulator = Triangulator::from_points(&points).unwrap();
+        while triangulator.do_step(&points) {}
+
+        assert_eq!(triangulator.one_ring(0), vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(triangulator.one_ring(1), vec![0, 2, 6]);
+    }
+
+    #[test]
+    fn triangles_around_interior_vertex() {
+        let mut points = vec![Point::new(0.1, 0.0)];
+        for i in 0..6 {
+            let angle = i as f32 * std::f32::consts::PI / 3.0;
+            points.push(Point::new(angle.cos(), angle.sin()));
         }
+
+        let mut triangulator = Triangulator::from_points(&points).unwrap();
+        while triangulator.do_step(&points) {}
+
+        let around = triangulator.triangles_around(0);
+        assert_eq!(around.len(), triangulator.one_ring(0).len());
+        assert_eq!(around, (0..6).collect::<Vec<_>>());
+        assert_eq!(triangulator.triangles_around(1).len(), 2);
+    }
+
+    #[test]
+    fn full_matches_separate_calls() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+        let mut rng = StdRng::seed_from_u64(21);
+        let points = (0..60)
+            .map(|_| Point::new(rng.gen_range(0.0..10.0), rng.gen_range(0.0..10.0)))
+            .collect::<Vec<_>>();
+
+        let full = triangulate_full(&points).unwrap();
+        let (triangles, neighbours) = triangulate_with_neighbours(&points).unwrap();
+
+        assert_eq!(full.triangles, triangulate(&points).unwrap());
+        assert_eq!(full.triangles, triangles);
+        assert_eq!(full.neighbours, neighbours);
+
+        let boundary = crate::boundary_edges(&triangles)
+            .into_iter()
+            .map(|(a, b)| Edge::new(a, b))
+            .collect::<Vec<_>>();
+        assert_eq!(full.boundary, boundary);
+
+        let mut edges = triangles
+            .iter()
+            .flat_map(|tri| {
+                [
+                    Edge::new(tri.index0, tri.index1),
+                    Edge::new(tri.index1, tri.index2),
+                    Edge::new(tri.index2, tri.index0),
+                ]
+            })
+            .collect::<Vec<_>>();
+        edges.sort_unstable();
+        edges.dedup();
+        assert_eq!(full.edges, edges);
+    }
+
+    #[test]
+    fn voronoi_cells_of_grid() {
+        let points = (0..25)
+            .map(|i| Point::new((i % 5) as f32 * 2.0, (i / 5) as f32 * 2.0))
+            .collect::<Vec<_>>();
+
+        let mut triangulator = Triangulator::from_points(&points).unwrap();
+        while triangulator.do_step(&points) {}
+
+        let areas = triangulator.voronoi_cell_areas(&points);
+        for (idx, area) in areas.iter().enumerate() {
+            let (x, y) = (idx % 5, idx / 5);
+            if x == 0 || x == 4 || y == 0 || y == 4 {
+                assert!(area.is_none());
+            } else {
+                assert!((area.unwrap() - 4.0).abs() < 1e-4, "{:?}", area);
+            }
+        }
+    }
+
+    #[test]
+    fn circumcenter_same_as_circle() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(3.0, 0.5),
+            Point::new(1.0, 2.0),
+            Point::new(2.5, 2.5),
+        ];
+
+        let mut triangulator = Triangulator::from_points(&points).unwrap();
+        while triangulator.do_step(&points) {}
+
+        for (tri_idx, tri) in triangulator.iter().enumerate() {
+            let center = triangulator.circumcenter(&points, tri_idx as TriIdx);
+            let circle = Circle::from_triangle(tri, &points);
+            assert_eq!((center.x, center.y), (circle.pos.x, circle.pos.y));
+        }
+    }
+
+    #[test]
+    fn triangles_of_interior_and_boundary_edge() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(0.0, 1.0),
+            Point::new(0.4, 0.5),
+        ];
+
+        let mut triangulator = Triangulator::from_points(&points).unwrap();
+        while triangulator.do_step(&points) {}
+
+        let interior = triangulator.triangles_of_edge(Edge::new(4, 0));
+        assert_eq!(interior.len(), 2);
+        for tri_idx in interior {
+            let tri = &triangulator.get_triangles()[tri_idx as usize];
+            assert!([tri.index0, tri.index1, tri.index2].contains(&4));
+            assert!([tri.index0, tri.index1, tri.index2].contains(&0));
+        }
+        assert_eq!(triangulator.triangles_of_edge(Edge::new(0, 1)).len(), 1);
+        assert!(triangulator.triangles_of_edge(Edge::new(0, 2)).is_empty());
+        assert!(triangulator.triangles_of_edge(Edge::new(0, 17)).is_empty());
+    }
+
+    #[test]
+    fn boundary_edges_of_square() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(0.0, 1.0),
+            Point::new(0.4, 0.5),
+        ];
+
+        let mut triangulator = Triangulator::from_points(&points).unwrap();
+        while triangulator.do_step(&points) {}
+
+        for (a, b) in [(0, 1), (1, 2), (2, 3), (3, 0)] {
+            assert!(triangulator.is_boundary_edge(Edge::new(a, b)));
+        }
+        for a in 0..4 {
+            assert!(!triangulator.is_boundary_edge(Edge::new(a, 4)));
+        }
+        assert!(!triangulator.is_boundary_edge(Edge::new(0, 2)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize_mid_build_and_resume() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+            Point::new(0.5, 1.5),
+            Point::new(1.2, 0.7),
+            Point::new(1.5, 1.6),
+        ];
+
+        let expected = triangulate(&points).unwrap();
+
+        let mut triangulator = Triangulator::from_points(&points).unwrap();
+        triangulator.do_step(&points);
+        let json = serde_json::to_string(&triangulator).unwrap();
+        drop(triangulator);
+
+        let mut restored: Triangulator = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.points_remaining(), 2);
+        while restored.do_step(&points) {}
+
+        assert_eq!(restored.num_triangles(), expected.len());
+        for tri in restored.get_triangles() {
+            assert!(expected.iter().any(|e| e.equivalent(tri)));
+        }
+    }
+
+    #[test]
+    fn fixed_seed_is_reproducible() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(1);
+        let points = (0..50)
+            .map(|_| Point::new(rng.gen_range(0.0..10.0), rng.gen_range(0.0..10.0)))
+            .collect::<Vec<_>>();
+
+        let run = || {
+            let mut triangulator = Triangulator::new();
+            triangulator.set_insertion_order(InsertionOrder::Random(42));
+            triangulator.initial_triangulation(&points).unwrap();
+            while triangulator.do_step(&points) {}
+            triangulator.get_triangles().to_vec()
+        };
+
+        assert_eq!(run(), run());
+    }
+
+    #[test]
+    fn default_triangulation_is_reproducible() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        // on a grid, so that cocircular ties are broken by the order of hash container iteration
+        let mut rng = StdRng::seed_from_u64(2);
+        let points = (0..200)
+            .map(|_| Point::new(rng.gen_range(0..15) as f32, rng.gen_range(0..15) as f32))
+            .collect::<Vec<_>>();
+
+        assert_eq!(triangulate(&points), triangulate(&points));
+    }
+
+    #[test]
+    fn undo_step_restores_triangles() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+            Point::new(0.5, 1.5),
+            Point::new(1.2, 0.7),
+            Point::new(1.5, 1.6),
+        ];
+
+        let mut triangulator = Triangulator::from_points(&points).unwrap();
+        let mut snapshots = vec![triangulator.get_triangles().to_vec()];
+        while triangulator.do_step(&points) {
+            snapshots.push(triangulator.get_triangles().to_vec());
+        }
+        let last = snapshots.pop().unwrap();
+
+        while let Some(snapshot) = snapshots.pop() {
+            assert!(triangulator.undo_step());
+            assert_eq!(triangulator.get_triangles(), snapshot.as_slice());
+        }
+        assert!(!triangulator.undo_step());
+        assert_eq!(triangulator.points_remaining(), 3);
+
+        while triangulator.do_step(&points) {}
+        assert_eq!(triangulator.get_triangles(), last.as_slice());
+    }
+
+    #[test]
+    fn collapse_edge_removes_two_triangles() {
+        let mut points = vec![Point::new(-0.2, 0.05), Point::new(0.2, -0.05)];
+        for i in 0..6 {
+            let angle = i as f32 * std::f32::consts::PI / 3.0;
+            points.push(Point::new(angle.cos(), angle.sin()));
+        }
+
+        let mut triangulator = Triangulator::from_points(&points).unwrap();
+        while triangulator.do_step(&points) {}
+        let num_triangles = triangulator.num_triangles();
+        let area = triangulator.total_area(&points);
+
+        triangulator
+            .collapse_edge(Edge::new(0, 1), &mut points)
+            .unwrap();
+
+        assert_eq!(triangulator.num_triangles(), num_triangles - 2);
+        assert!((triangulator.total_area(&points) - area).abs() < 1e-5);
+        assert!(triangulator.triangle_areas(&points).all(|a| a > 0.0));
+        assert!(triangulator.triangles_around(1).is_empty());
+        assert_eq!(triangulator.one_ring(0), vec![2, 3, 4, 5, 6, 7]);
+        assert_eq!(
+            triangulator.collapse_edge(Edge::new(0, 1), &mut points),
+            Err(CollapseError::UnknownEdge)
+        );
+    }
+
+    #[test]
+    fn vertex_neighbours_include_edge_neighbours() {
+        let mut points = vec![Point::new(0.1, 0.0), Point::new(0.5, 0.3)];
+        for i in 0..8 {
+            let angle = i as f32 * std::f32::consts::PI / 4.0;
+            points.push(Point::new(2.0 * angle.cos(), 2.0 * angle.sin()));
+        }
+        let mut triangulator = Triangulator::from_points(&points).unwrap();
+        while triangulator.do_step(&points) {}
+
+        let interior = triangulator
+            .iter()
+            .position(|t| {
+                let vertices = [t.index0, t.index1, t.index2];
+                vertices.contains(&0) && vertices.contains(&1)
+            })
+            .unwrap() as TriIdx;
+        let vertex_neighbours = triangulator.triangle_vertex_neighbours(interior);
+        let edge_neighbours = triangulator.neighbours()[interior as usize];
+
+        assert!(!vertex_neighbours.contains(&interior));
+        for neighbour in edge_neighbours.iter().flatten() {
+            assert!(vertex_neighbours.contains(neighbour));
+        }
+        assert!(vertex_neighbours.len() > edge_neighbours.iter().flatten().count());
+    }
+
+    #[test]
+    fn boundary_triangles_touch_hull() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(8);
+        let points = (0..40)
+            .map(|_| Point::new(rng.gen_range(0.0..10.0), rng.gen_range(0.0..10.0)))
+            .collect::<Vec<_>>();
+        let mut triangulator = Triangulator::from_points(&points).unwrap();
+        while triangulator.do_step(&points) {}
+
+        let hull_edges = boundary_edges(triangulator.get_triangles());
+        let on_hull = |tri: &Triangle| {
+            [
+                Edge::new(tri.index0, tri.index1),
+                Edge::new(tri.index1, tri.index2),
+                Edge::new(tri.index2, tri.index0),
+            ]
+            .iter()
+            .any(|e| hull_edges.contains(&(e.index_0, e.index_1)))
+        };
+        let boundary_triangles = triangulator.boundary_triangles();
+
+        assert!(!boundary_triangles.is_empty());
+        assert!(boundary_triangles.len() < triangulator.num_triangles());
+        for (idx, tri) in triangulator.iter().enumerate() {
+            assert_eq!(boundary_triangles.contains(&(idx as TriIdx)), on_hull(tri));
+        }
+    }
+
+    #[test]
+    fn triangles_in_bounds_cover_rectangle() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(10);
+        let mut points = (0..30)
+            .map(|_| Point::new(rng.gen_range(2.0..8.0), rng.gen_range(3.0..5.0)))
+            .collect::<Vec<_>>();
+        let (min, max) = (Point::new(0.0, 0.0), Point::new(10.0, 6.0));
+
+        let triangles = Triangulator::triangulate_in_bounds(&points, min, max).unwrap();
+
+        let mut all_points = vec![min, Point::new(10.0, 0.0), max, Point::new(0.0, 6.0)];
+        all_points.extend_from_slice(&points);
+        let triangulator = Triangulator::from_existing(triangles, &all_points).unwrap();
+        assert!((triangulator.total_area(&all_points) - 60.0).abs() < 1e-3);
+        assert!(triangulator.triangle_areas(&all_points).all(|a| a > 0.0));
+        let mut hull = boundary_polygon(triangulator.get_triangles());
+        hull.sort_unstable();
+        assert_eq!(hull, [0, 1, 2, 3]);
+
+        points.push(Point::new(10.5, 1.0));
+        assert_eq!(
+            Triangulator::triangulate_in_bounds(&points, min, max),
+            Err(TriangulatorError::PointOutsideOfBounds(30))
+        );
+
+        points[30] = Point::new(f32::NAN, 1.0);
+        assert_eq!(
+            Triangulator::triangulate_in_bounds(&points, min, max),
+            Err(TriangulatorError::NANInInput(30))
+        );
+        assert_eq!(
+            Triangulator::triangulate_in_bounds(&points, max, min),
+            Err(TriangulatorError::InvalidBounds)
+        );
+    }
+
+    #[test]
+    fn opposite_triangles_are_distinct_neighbours() {
+        let mut points = vec![Point::new(0.1, 0.0), Point::new(0.5, 0.3)];
+        for i in 0..8 {
+            let angle = i as f32 * std::f32::consts::PI / 4.0;
+            points.push(Point::new(2.0 * angle.cos(), 2.0 * angle.sin()));
+        }
+        let mut triangulator = Triangulator::from_points(&points).unwrap();
+        while triangulator.do_step(&points) {}
+        let neighbours = triangulator.neighbours();
+
+        for (idx, tri) in triangulator.iter().enumerate() {
+            let idx = idx as TriIdx;
+            let mut opposite = [
+                Edge::new(tri.index0, tri.index1),
+                Edge::new(tri.index1, tri.index2),
+                Edge::new(tri.index2, tri.index0),
+            ]
+            .iter()
+            .filter_map(|edge| triangulator.opposite_triangle(idx, *edge))
+            .collect::<Vec<_>>();
+            let mut expected = neighbours[idx as usize]
+                .iter()
+                .flatten()
+                .copied()
+                .collect::<Vec<_>>();
+
+            opposite.sort_unstable();
+            expected.sort_unstable();
+            assert_eq!(opposite, expected);
+            assert!(!opposite.contains(&idx));
+        }
+        assert_eq!(triangulator.opposite_triangle(0, Edge::new(0, 99)), None);
+    }
+
+    #[test]
+    fn non_delaunay_edge_reported() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(2.0, 1.0),
+            Point::new(2.0, -1.0),
+        ];
+        let mut triangulator = Triangulator::from_existing(
+            vec![Triangle::new(0, 1, 2), Triangle::new(0, 3, 1)],
+            &points,
+        )
+        .unwrap();
+
+        assert_eq!(triangulator.non_delaunay_edges(&points), [Edge::new(0, 1)]);
+
+        triangulator.flip_edge(Edge::new(0, 1), &points).unwrap();
+        assert!(triangulator.non_delaunay_edges(&points).is_empty());
+
+        let mut rng = StdRng::seed_from_u64(12);
+        let points = (0..100)
+            .map(|_| Point::new(rng.gen_range(0.0..10.0), rng.gen_range(0.0..10.0)))
+            .collect::<Vec<_>>();
+        let mut triangulator = Triangulator::from_points(&points).unwrap();
+        while triangulator.do_step(&points) {}
+        assert!(triangulator.non_delaunay_edges(&points).is_empty());
+    }
+
+    #[test]
+    fn topology_of_small_meshes() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(0.0, 1.0),
+            Point::new(0.4, 0.6),
+            Point::new(5.0, 0.0),
+            Point::new(6.0, 0.0),
+            Point::new(5.0, 1.0),
+        ];
+        let mut triangulator = Triangulator::from_points(&points[..5]).unwrap();
+        while triangulator.do_step(&points[..5]) {}
+
+        assert_eq!(
+            triangulator.topology(),
+            Topology {
+                num_vertices: 5,
+                num_edges: 8,
+                num_triangles: 4,
+                num_components: 1,
+                euler_characteristic: 1,
+            }
+        );
+
+        let mut triangles = triangulator.into_triangles();
+        triangles.push(Triangle::new(5, 6, 7));
+        let triangulator = Triangulator::from_existing(triangles, &points).unwrap();
+        let topology = triangulator.topology();
+        assert_eq!(topology.num_components, 2);
+        assert_eq!(topology.euler_characteristic, 2);
+    }
+
+    #[test]
+    fn merging_halves_same_as_whole() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(14);
+        let mut points = (0..60)
+            .map(|_| Point::new(rng.gen_range(0.0..10.0), rng.gen_range(0.0..10.0)))
+            .collect::<Vec<_>>();
+        // the halves share the points on the seam
+        for y in 0..=10 {
+            points.push(Point::new(5.0, y as f32));
+        }
+        let half = |in_half: &dyn Fn(&Point) -> bool| {
+            let index_map = (0..points.len() as PointIdx)
+                .filter(|idx| in_half(&points[*idx as usize]))
+                .collect::<Vec<_>>();
+            let half_points = index_map
+                .iter()
+                .map(|idx| points[*idx as usize])
+                .collect::<Vec<_>>();
+            Triangulation {
+                triangles: triangulate(&half_points).unwrap(),
+                index_map,
+            }
+        };
+        let left = half(&|p| p.x <= 5.0);
+        let right = half(&|p| p.x >= 5.0);
+
+        let merged = merge(left.clone(), right, &points).unwrap();
+
+        let expected = triangulate(&points).unwrap();
+        assert_eq!(
+            merged.index_map,
+            (0..points.len() as PointIdx).collect::<Vec<_>>()
+        );
+        assert_eq!(merged.triangles.len(), expected.len());
+        for tri in merged.triangles.iter() {
+            assert!(expected.iter().any(|e| e.equivalent(tri)));
+        }
+        assert_eq!(
+            merge(left.clone(), left, &points),
+            Err(TriangulatorError::InvalidMesh)
+        );
+    }
+
+    #[test]
+    fn reset_to_hull_reproduces_mesh() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(16);
+        let points = (0..50)
+            .map(|_| Point::new(rng.gen_range(0.0..10.0), rng.gen_range(0.0..10.0)))
+            .collect::<Vec<_>>();
+        let mut triangulator = Triangulator::from_points(&points).unwrap();
+        let hull_triangles = triangulator.get_triangles().to_vec();
+        let points_remaining = triangulator.points_remaining();
+        while triangulator.do_step(&points) {}
+        let expected = triangulator.get_triangles().to_vec();
+
+        assert!(triangulator.reset_to_hull(&points));
+
+        assert_eq!(triangulator.get_triangles(), hull_triangles.as_slice());
+        assert_eq!(triangulator.points_remaining(), points_remaining);
+        while triangulator.do_step(&points) {}
+        assert_eq!(triangulator.get_triangles(), expected.as_slice());
+        assert!(triangulator.validate(&points).is_ok());
+
+        let mut existing = Triangulator::from_existing(expected, &points).unwrap();
+        assert!(!existing.reset_to_hull(&points));
+    }
+
+    #[test]
+    fn shortest_and_longest_edge() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(5.0, 3.0),
+            Point::new(5.0, 2.9),
+        ];
+        let mut triangulator = Triangulator::from_points(&points).unwrap();
+        while triangulator.do_step(&points) {}
+
+        assert_eq!(
+            triangulator.edge_length_extremes(&points),
+            Some((Edge::new(2, 3), Edge::new(0, 1)))
+        );
+        assert_eq!(Triangulator::new().edge_length_extremes(&points), None);
+    }
+
+    #[test]
+    fn moving_vertex_keeps_delaunay() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(18);
+        let mut points = (0..100)
+            .map(|_| Point::new(rng.gen_range(0.0..10.0), rng.gen_range(0.0..10.0)))
+            .collect::<Vec<_>>();
+        let mut triangulator = Triangulator::from_points(&points).unwrap();
+        while triangulator.do_step(&points) {}
+        let vertex = (0..points.len() as PointIdx)
+            .min_by(|a, b| {
+                let center = Point::new(5.0, 5.0);
+                let distance = |idx: &PointIdx| points[*idx as usize].distance(&center);
+                distance(a).total_cmp(&distance(b))
+            })
+            .unwrap();
+
+        // a little, keeping the orientation of its triangles, only the neighbourhood changes
+        let before = triangulator.get_triangles().to_vec();
+        let mut neighbourhood = triangulator.one_ring(vertex);
+        neighbourhood.push(vertex);
+        let near = |tri: &Triangle| {
+            [tri.index0, tri.index1, tri.index2]
+                .iter()
+                .any(|idx| neighbourhood.contains(idx))
+        };
+        let nudged = points[vertex as usize] + &Point::new(0.15, 0.2);
+        triangulator
+            .move_vertex(&mut points, vertex, nudged)
+            .unwrap();
+        assert!(triangulator.validate(&points).is_ok());
+        assert!(triangulator.non_delaunay_edges(&points).is_empty());
+        let changed = triangulator
+            .iter()
+            .filter(|tri| !before.iter().any(|b| b.equivalent(tri)))
+            .collect::<Vec<_>>();
+        assert!(!changed.is_empty());
+        assert!(changed.iter().all(|tri| near(tri)));
+        for tri in before.iter().filter(|tri| !near(tri)) {
+            assert!(triangulator.iter().any(|t| t.equivalent(tri)));
+        }
+
+        // across the mesh, reinserted
+        triangulator
+            .move_vertex(&mut points, vertex, Point::new(1.5, 8.5))
+            .unwrap();
+        assert!(triangulator.validate(&points).is_ok());
+        assert!(triangulator.non_delaunay_edges(&points).is_empty());
+        assert_eq!(
+            triangulator.num_triangles(),
+            triangulate(&points).unwrap().len()
+        );
+
+        assert_eq!(
+            triangulator.move_vertex(&mut points, vertex, Point::new(20.0, 5.0)),
+            Err(TriangulatorError::PointOutsideOfHull)
+        );
+        assert_eq!(points[vertex as usize].x, 1.5);
+
+        // on the hull, even a small move could make it concave
+        let hull_vertex = (0..points.len() as PointIdx)
+            .min_by(|a, b| points[*a as usize].x.total_cmp(&points[*b as usize].x))
+            .unwrap();
+        let hull_position = points[hull_vertex as usize];
+        let before = triangulator.get_triangles().to_vec();
+        assert_eq!(
+            triangulator.move_vertex(
+                &mut points,
+                hull_vertex,
+                hull_position + &Point::new(0.01, 0.0)
+            ),
+            Err(TriangulatorError::PointOutsideOfHull)
+        );
+        assert_eq!(points[hull_vertex as usize].x, hull_position.x);
+        assert_eq!(triangulator.get_triangles(), &before[..]);
+    }
+
+    #[test]
+    fn decimating_grid_keeps_boundary() {
+        let mut points = Vec::new();
+        for y in 0..20 {
+            for x in 0..20 {
+                // a slight jitter, so the grid isn't all cocircular
+                let jitter = ((x * 7 + y * 13) % 5) as f32 * 0.01;
+                points.push(Point::new(x as f32 + jitter, y as f32 - jitter));
+            }
+        }
+        let mut triangulator = Triangulator::from_points(&points).unwrap();
+        while triangulator.do_step(&points) {}
+        let boundary = boundary_polygon(triangulator.get_triangles())
+            .iter()
+            .map(|idx| points[*idx as usize])
+            .collect::<Vec<_>>();
+        let area = triangulator.total_area(&points);
+
+        triangulator.decimate(&mut points, 100);
+
+        assert_eq!(points.len(), 100);
+        assert!(triangulator.validate(&points).is_ok());
+        assert!((triangulator.total_area(&points) - area).abs() < 1e-3 * area);
+        let decimated_boundary = boundary_polygon(triangulator.get_triangles());
+        assert_eq!(decimated_boundary.len(), boundary.len());
+        for p in boundary.iter() {
+            assert!(decimated_boundary
+                .iter()
+                .any(|idx| points[*idx as usize].distance(p) == 0.0));
+        }
+
+        // below the number of boundary points, only they are left
+        triangulator.decimate(&mut points, 3);
+        assert_eq!(points.len(), boundary.len());
+    }
+
+    #[test]
+    fn decimating_heights_keeps_peak() {
+        let mut points = Vec::new();
+        let mut heights = Vec::new();
+        for y in 0..10 {
+            for x in 0..10 {
+                let jitter = ((x * 7 + y * 13) % 5) as f32 * 0.01;
+                points.push(Point::new(x as f32 + jitter, y as f32 - jitter));
+                heights.push(if (x, y) == (4, 5) {
+                    3.0
+                } else {
+                    0.5 * x as f32
+                });
+            }
+        }
+        let peak = points[54];
+        let mut triangulator = Triangulator::from_points(&points).unwrap();
+        while triangulator.do_step(&points) {}
+
+        triangulator.decimate_with_heights(&mut points, &mut heights, 40);
+
+        assert_eq!(points.len(), 40);
+        assert_eq!(heights.len(), 40);
+        let peak_idx = points
+            .iter()
+            .position(|p| p.distance(&peak) == 0.0)
+            .unwrap();
+        assert_eq!(heights[peak_idx], 3.0);
+    }
+
+    #[test]
+    fn equal_weights_same_as_unweighted() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(2);
+        let points = (0..30)
+            .map(|_| Point::new(rng.gen_range(0.0..10.0), rng.gen_range(0.0..10.0)))
+            .collect::<Vec<_>>();
+        let weights = vec![0.5; points.len()];
+
+        let expected = triangulate(&points).unwrap();
+        let triangles = triangulate_weighted(&points, &weights).unwrap();
+
+        assert_eq!(triangles.len(), expected.len());
+        for tri in triangles.iter() {
+            assert!(expected.iter().any(|e| e.equivalent(tri)));
+        }
+        assert_eq!(
+            triangulate_weighted(&points, &weights[1..]),
+            Err(TriangulatorError::WeightCountMismatch)
+        );
+    }
+
+    #[test]
+    fn heavy_weight_changes_diagonal() {
+        // a square is ambiguous, a heavy corner claims the diagonal
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(0.0, 1.0),
+        ];
+
+        for heavy in 0..4 {
+            let mut weights = [0.0; 4];
+            weights[heavy] = 0.5;
+            let heavy = heavy as PointIdx;
+            let triangles = triangulate_weighted(&points, &weights).unwrap();
+            assert!(triangles
+                .iter()
+                .all(|tri| { tri.index0 == heavy || tri.index1 == heavy || tri.index2 == heavy }));
+        }
+    }
+
+    #[test]
+    fn max_triangle_count_matches_hand_counts() {
+        assert_eq!(max_triangle_count(3, 3), 1);
+        assert_eq!(max_triangle_count(4, 3), 3);
+        assert_eq!(max_triangle_count(4, 4), 2);
+        assert_eq!(max_triangle_count(5, 4), 4);
+        assert_eq!(max_triangle_count(0, 0), 0);
+
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(1.0, 2.0),
+            Point::new(1.0, 0.5),
+            Point::new(1.2, 0.8),
+        ];
+        assert_eq!(
+            triangulate(&points).unwrap().len(),
+            max_triangle_count(5, 3)
+        );
+    }
+
+    #[test]
+    fn offending_points() {
+        use rand::seq::SliceRandom;
+
+        // these are offending combinations of points, caused triangulate to fail:
+        let fails = [
+            Point::new(22., 20.),
+            Point::new(141., 20.),
+            Point::new(245., 169.),
+            Point::new(268., 134.),
+            Point::new(314., 133.),
+            Point::new(69., 20.),
+        ];
+
+        for _ in 0..720 {
+            let subset: Vec<Point> = fails
+                .choose_multiple(&mut rand::thread_rng(), 6)
+                .copied()
+                .collect();
+
+            let triangles = triangulate(&subset);
+            assert!(triangles.is_ok());
+        }
+    }
+
+    #[test]
+    fn triangulate_i32_same_as_float() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(4);
+        for _ in 0..50 {
+            let mut int_points = (0..20)
+                .map(|_| (rng.gen_range(-100..100), rng.gen_range(-100..100)))
+                .collect::<Vec<(i32, i32)>>();
+            int_points.sort_unstable();
+            int_points.dedup();
+            let points = int_points
+                .iter()
+                .map(|&(x, y)| Point::new(x as f32, y as f32))
+                .collect::<Vec<_>>();
+
+            let expected = triangulate(&points).unwrap();
+            let triangles = triangulate_i32(&int_points).unwrap();
+
+            // cocircular points can be triangulated either way, so check the delaunay property instead of equality
+            assert_eq!(triangles.len(), expected.len());
+            for tri in triangles.iter() {
+                let circle = Circle::from_triangle(tri, &points);
+                for p in points.iter() {
+                    let d_sqr = (p.x - circle.pos.x).powi(2) + (p.y - circle.pos.y).powi(2);
+                    assert!(d_sqr >= circle.radius_sqr * (1.0 - 1e-4));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn triangulate_i32_rejects_huge_coordinates() {
+        let points = [(0, 0), (1, 0), (0, 1 << 25)];
+
+        assert_eq!(
+            triangulate_i32(&points),
+            Err(TriangulatorError::CoordinateOutOfRange(2))
+        );
+    }
+
+    #[test]
+    fn triangulate_i32_too_few_points() {
+        assert_eq!(triangulate_i32(&[]), Err(TriangulatorError::TooFewPoints));
+        assert_eq!(
+            triangulate_i32(&[(0, 0), (1, 1 << 25)]),
+            Err(TriangulatorError::TooFewPoints)
+        );
+    }
+
+    #[test]
+    fn iterates_triangles() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(0.0, 1.0),
+            Point::new(0.3, 0.6),
+        ];
+        let mut triangulator = Triangulator::new();
+        triangulator.initial_triangulation(&points).unwrap();
+        while triangulator.do_step(&points) {}
+        let expected = triangulator.get_triangles().to_vec();
+
+        assert_eq!(triangulator.iter().cloned().collect::<Vec<_>>(), expected);
+        let mut borrowed = Vec::new();
+        for tri in &triangulator {
+            borrowed.push(tri.clone());
+        }
+        assert_eq!(borrowed, expected);
+        assert_eq!(triangulator.into_iter().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn into_triangles_moves_result_out() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.5, 1.0),
+            Point::new(0.5, 0.4),
+        ];
+        let mut triangulator = Triangulator::new();
+        triangulator.initial_triangulation(&points).unwrap();
+        while triangulator.do_step(&points) {}
+        let expected = triangulator.get_triangles().to_vec();
+        let ptr = triangulator.get_triangles().as_ptr();
+
+        let triangles = triangulator.into_triangles();
+
+        assert_eq!(triangles, expected);
+        assert_eq!(triangles.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn displays_summary() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.5, 1.0),
+            Point::new(0.5, 0.4),
+        ];
+        let mut triangulator = Triangulator::new();
+        triangulator.initial_triangulation(&points).unwrap();
+
+        assert_eq!(
+            triangulator.to_string(),
+            "Triangulator { triangles: 1, points_remaining: 1 }"
+        );
+
+        while triangulator.do_step(&points) {}
+        assert_eq!(
+            triangulator.to_string(),
+            "Triangulator { triangles: 3, points_remaining: 0 }"
+        );
+    }
+
+    #[test]
+    fn contains_point_inside_on_edge_and_outside() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+            Point::new(0.8, 1.1),
+        ];
+        let mut triangulator = Triangulator::new();
+        triangulator.initial_triangulation(&points).unwrap();
+        while triangulator.do_step(&points) {}
+
+        assert!(triangulator.contains_point(&points, &Point::new(1.5, 0.5)));
+        assert!(triangulator.contains_point(&points, &Point::new(1.0, 0.0)));
+        assert!(triangulator.contains_point(&points, &Point::new(2.0, 2.0)));
+        assert!(!triangulator.contains_point(&points, &Point::new(2.1, 1.0)));
+        assert!(!triangulator.contains_point(&points, &Point::new(-1.0, -1.0)));
+    }
+
+    #[test]
+    fn bounding_box_of_used_points() {
+        let points = [
+            Point::new(-1.0, 0.5),
+            Point::new(3.0, 0.0),
+            Point::new(2.0, 4.0),
+            Point::new(0.0, 3.0),
+            Point::new(1.0, 1.0),
+        ];
+        let mut triangulator = Triangulator::new();
+        assert!(triangulator.bounding_box(&points).is_none());
+
+        triangulator.initial_triangulation(&points).unwrap();
+        while triangulator.do_step(&points) {}
+        let (min, max) = triangulator.bounding_box(&points).unwrap();
+
+        assert_eq!((min.x, min.y), (-1.0, 0.0));
+        assert_eq!((max.x, max.y), (3.0, 4.0));
+    }
+
+    #[test]
+    fn validates_mesh() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+            Point::new(0.8, 1.1),
+            Point::new(1.0, 0.0),
+        ];
+        let mut triangulator = Triangulator::new();
+        triangulator.initial_triangulation(&points).unwrap();
+        assert_eq!(triangulator.validate(&points), Ok(()));
+        while triangulator.do_step(&points) {}
+        assert_eq!(triangulator.validate(&points), Ok(()));
+
+        // corrupt the triangles behind the edge mapping's back
+        let mut corrupted = triangulator.clone();
+        let copy = corrupted.triangulator.triangles[0].clone();
+        corrupted.triangulator.triangles[1] = copy;
+        let issues = corrupted.validate(&points).unwrap_err();
+        assert!(issues.contains(&ValidationIssue::DuplicateTriangle(0, 1)));
+        assert!(issues.contains(&ValidationIssue::InconsistentMapping(1)));
+
+        let mut corrupted = triangulator.clone();
+        corrupted.triangulator.triangles[0] = Triangle::new(0, 5, 1);
+        let issues = corrupted.validate(&points).unwrap_err();
+        assert!(issues.contains(&ValidationIssue::DegenerateTriangle(0)));
+        assert!(issues.contains(&ValidationIssue::InconsistentMapping(0)));
+    }
+
+    #[test]
+    fn terminates_on_near_cocircular_points() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(5);
+        for _ in 0..20 {
+            let mut points = (0..64)
+                .map(|i| {
+                    let angle = i as f32 / 64.0 * std::f32::consts::TAU;
+                    let radius = 1.0 + rng.gen_range(-1e-6..1e-6);
+                    Point::new(radius * angle.cos(), radius * angle.sin())
+                })
+                .collect::<Vec<_>>();
+            points.push(Point::new(0.0, 0.0));
+
+            let triangles = triangulate(&points).unwrap();
+            assert_eq!(triangles.len(), 64);
+        }
+    }
+
+    #[test]
+    fn splits_shared_edge() {
+        // the center is on the diagonal of the initial two triangles, the rest on edges of later ones
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+            Point::new(1.0, 1.0),
+            Point::new(0.5, 0.5),
+            Point::new(1.5, 1.5),
+        ];
+        let mut triangulator = Triangulator::new();
+        triangulator.initial_triangulation(&points).unwrap();
+        while triangulator.do_step(&points) {
+            assert_eq!(triangulator.validate(&points), Ok(()));
+        }
+
+        assert_eq!(triangulator.num_triangles(), 8);
+        for tri in triangulator.iter() {
+            let circle = Circle::from_triangle(tri, &points);
+            for p in points.iter() {
+                let d_sqr = (p.x - circle.pos.x).powi(2) + (p.y - circle.pos.y).powi(2);
+                assert!(d_sqr >= circle.radius_sqr - 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn skips_coincident_points() {
+        let mut points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+            Point::new(0.8, 1.1),
+        ];
+        let expected = triangulate(&points).unwrap();
+
+        points.push(Point::new(0.8, 1.1));
+        let mut triangulator = Triangulator::new();
+        // so that the later of the two is the one left out
+        triangulator.set_insertion_order(InsertionOrder::AsGiven);
+        triangulator.initial_triangulation(&points).unwrap();
+        while triangulator.do_step(&points) {}
+
+        assert_eq!(triangulator.num_triangles(), expected.len());
+        assert_eq!(triangulator.validate(&points), Ok(()));
+        assert_eq!(triangulator.merged_points(), &[(5, 4)]);
+
+        assert!(triangulator.undo_step());
+        assert!(triangulator.merged_points().is_empty());
     }
 }
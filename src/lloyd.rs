@@ -0,0 +1,99 @@
+use crate::{
+    boundary::boundary_polygon,
+    polygon::point_in_polygon,
+    triangulate,
+    types::{HashSet, PointIdx},
+    voronoi::{cell_corners, cell_moments},
+    Point, TriangulatorError,
+};
+
+/// lloyd relaxation: moves each interior point to the centroid of its voronoi cell, iterations times,
+/// re-triangulating in between. the result approaches a centroidal voronoi tessellation, ie evenly spaced points.
+/// hull points are fixed so the domain doesn't shrink, and a point is left in place if its centroid is outside the hull
+pub fn lloyd_relax(points: &mut [Point], iterations: usize) -> Result<(), TriangulatorError> {
+    for _ in 0..iterations {
+        let triangles = triangulate(points)?;
+        let hull = boundary_polygon(&triangles);
+        let on_hull = hull.iter().copied().collect::<HashSet<_>>();
+
+        let mut cells = cell_corners(points, &triangles);
+
+        for (idx, cell) in cells.iter_mut().enumerate() {
+            if cell.len() < 3 || on_hull.contains(&(idx as PointIdx)) {
+                continue;
+            }
+            if let Some(centroid) = cell_centroid(&points[idx], cell) {
+                if point_in_polygon(&centroid, &hull, points) {
+                    points[idx] = centroid;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// centroid of the convex cell around site, with corners in any order
+fn cell_centroid(site: &Point, corners: &mut [Point]) -> Option<Point> {
+    let (area, sums) = cell_moments(site, corners);
+    if area.abs() < f32::EPSILON || !area.is_finite() {
+        return None;
+    }
+    let scale = 1.0 / (3.0 * area);
+    Some(Point::new(site.x + sums.x * scale, site.y + sums.y * scale))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn min_distance(points: &[Point]) -> f32 {
+        let mut min = f32::MAX;
+        for i in 0..points.len() {
+            for j in i + 1..points.len() {
+                let d = points[i] - points[j];
+                min = min.min((d.x * d.x + d.y * d.y).sqrt());
+            }
+        }
+        min
+    }
+
+    #[test]
+    fn clustered_points_spread_out() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let mut points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(0.0, 1.0),
+        ];
+        points
+            .extend((0..20).map(|_| Point::new(rng.gen_range(0.4..0.6), rng.gen_range(0.4..0.6))));
+        let corners = points[..4].to_vec();
+
+        let before = min_distance(&points);
+        lloyd_relax(&mut points, 1).unwrap();
+        let after_one = min_distance(&points);
+        lloyd_relax(&mut points, 14).unwrap();
+        let after_all = min_distance(&points);
+
+        assert!(after_one > before);
+        assert!(after_all > after_one);
+        for (p, corner) in points.iter().zip(corners.iter()) {
+            assert!(p.x == corner.x && p.y == corner.y);
+        }
+        assert!(points
+            .iter()
+            .all(|p| (0.0..=1.0).contains(&p.x) && (0.0..=1.0).contains(&p.y)));
+    }
+
+    #[test]
+    fn too_few_points() {
+        let mut points = vec![Point::new(0.0, 0.0), Point::new(1.0, 0.0)];
+        assert_eq!(
+            lloyd_relax(&mut points, 1),
+            Err(TriangulatorError::TooFewPoints)
+        );
+    }
+}
@@ -0,0 +1,60 @@
+/// tuning of the geometric tests of the triangulation
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TriangulatorOptions {
+    /// tolerance for a point to count as on a line, eg on a triangle edge when locating the triangle containing it,
+    /// and for a triangle to count as degenerate.
+    /// it is relative: compared to the sine of the angle between the line and the point, not to a distance,
+    /// so it behaves the same regardless of coordinate magnitude. f32 rounding gives errors of around
+    /// f32::EPSILON relative to the coordinates, so a few times that takes in points off a line by rounding.
+    /// 0, the default, keeps the plain tests: a point is on a line only if its orientation is exactly 0, and a
+    /// triangle is degenerate if twice its area is below f32::EPSILON, which depends on coordinate magnitude.
+    /// not used for triangulate_i32, which is exact
+    pub geometry_epsilon: f32,
+
+    /// when to flip the shared edge of two triangles
+    pub flip_criterion: FlipCriterion,
+
+    /// give CoincidentPoints for points at the same position, within geometry_epsilon relative to their coordinates,
+    /// instead of leaving out all but one. at the default geometry_epsilon of 0 only identical points are rejected.
+    /// checked when adding all points, eg by triangulate with these options, and by insert_point
+    pub reject_coincident: bool,
+
+    /// tolerance, relative as geometry_epsilon, for a nearly collinear point on the convex hull to stay on it.
+    /// 0, the default, keeps only points on or outside of the line between their neighbours, so float noise
+    /// decides for nearly collinear ones. a few times f32::EPSILON keeps all of them. the hull may then be concave
+    /// within the tolerance, so it is ear clipped instead of fanned, which is slower for large hulls.
+    /// not used for triangulate_i32
+    pub hull_epsilon: f32,
+
+    /// restoring the delaunay property after a change gives up after this many flips per triangle of the mesh,
+    /// and reports FlipLimitExceeded. that many flips means a bug, so this only guards against hanging on one
+    pub max_flips_per_triangle: usize,
+}
+
+/// the test deciding whether the diagonal of the quad formed by two triangles should be flipped
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FlipCriterion {
+    /// flip if the opposite point is inside the circumcircle. this maximizes the minimum angle,
+    /// giving the delaunay triangulation
+    #[default]
+    Delaunay,
+
+    /// flip if it lowers the largest angle of the two triangles, avoiding obtuse triangles, eg for interpolation.
+    /// the result is in general not delaunay. points are then inserted by splitting the triangle containing them
+    /// instead of the delaunay cavity, which makes building slower
+    MinMaxAngle,
+}
+
+impl Default for TriangulatorOptions {
+    fn default() -> Self {
+        Self {
+            geometry_epsilon: 0.0,
+            flip_criterion: FlipCriterion::default(),
+            reject_coincident: false,
+            hull_epsilon: 0.0,
+            max_flips_per_triangle: 32,
+        }
+    }
+}
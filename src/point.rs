@@ -3,7 +3,10 @@ use std::{
     ops::{Add, Mul, Sub},
 };
 
+// repr(C) so that interleaved x,y coordinates can be viewed as points, see points_from_flat
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
 pub struct Point {
     pub x: f32,
     pub y: f32,
@@ -13,12 +16,67 @@ impl Point {
     pub fn new(x: f32, y: f32) -> Self {
         Self { x, y }
     }
+
+    pub fn distance(&self, other: &Point) -> f32 {
+        (self.x - other.x).hypot(self.y - other.y)
+    }
+}
+
+/// views interleaved x,y coordinates as points, without copying. coords must have even length.
+pub(crate) fn points_from_flat(coords: &[f32]) -> &[Point] {
+    debug_assert!(coords.len().is_multiple_of(2));
+
+    // safe since Point is repr(C) with two f32 fields, thus same layout and alignment as [f32; 2]
+    unsafe { std::slice::from_raw_parts(coords.as_ptr() as *const Point, coords.len() / 2) }
 }
 
 pub(crate) fn cross(a: &Point, b: &Point, origin: &Point) -> f32 {
     (a.x - origin.x) * (b.y - origin.y) - (a.y - origin.y) * (b.x - origin.x)
 }
 
+// orient, but 0 when the sine of the angle at origin is within eps, see TriangulatorOptions
+pub(crate) fn orient_with_tolerance(
+    a: &Point,
+    b: &Point,
+    origin: &Point,
+    orient: fn(&Point, &Point, &Point) -> f32,
+    eps: f32,
+) -> f32 {
+    let det = orient(a, b, origin);
+    let scale = (a.x - origin.x).hypot(a.y - origin.y) * (b.x - origin.x).hypot(b.y - origin.y);
+    if det.abs() <= eps * scale {
+        0.0
+    } else {
+        det
+    }
+}
+
+/// turning direction of a -> b -> c, with y pointing up
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Orientation {
+    CounterClockwise,
+    Clockwise,
+    Collinear,
+}
+
+/// whether a, b, c turn counter clockwise, clockwise or are collinear.
+/// collinear if the cross product of b - a and c - a is within f32::EPSILON times the product of their lengths,
+/// ie if the sine of the angle at a is that small, so the tolerance follows the scale of the points.
+/// coincident points are collinear
+pub fn orientation(a: &Point, b: &Point, c: &Point) -> Orientation {
+    let det = cross(b, c, a);
+    let ab = (b.x - a.x).hypot(b.y - a.y);
+    let ac = (c.x - a.x).hypot(c.y - a.y);
+
+    if det.abs() <= f32::EPSILON * ab * ac {
+        Orientation::Collinear
+    } else if det > 0.0 {
+        Orientation::CounterClockwise
+    } else {
+        Orientation::Clockwise
+    }
+}
+
 impl<'a> Sub<&'a Point> for &'a Point {
     type Output = Point;
 
@@ -35,7 +93,7 @@ impl<'a> Sub<&'a Point> for Point {
     }
 }
 
-impl<'a> Sub<Point> for Point {
+impl Sub<Point> for Point {
     type Output = Point;
 
     fn sub(self, rhs: Point) -> Self::Output {
@@ -59,7 +117,7 @@ impl<'a> Add<&'a Point> for Point {
     }
 }
 
-impl<'a> Mul<f32> for Point {
+impl Mul<f32> for Point {
     type Output = Point;
 
     fn mul(self, rhs: f32) -> Self::Output {
@@ -73,3 +131,46 @@ impl Debug for Point {
         write!(f, "({}, {})", self.x, self.y)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orientation_of_turns() {
+        let a = Point::new(0.0, 0.0);
+        let b = Point::new(1.0, 0.0);
+
+        assert_eq!(
+            orientation(&a, &b, &Point::new(1.0, 1.0)),
+            Orientation::CounterClockwise
+        );
+        assert_eq!(
+            orientation(&a, &b, &Point::new(1.0, -1.0)),
+            Orientation::Clockwise
+        );
+        assert_eq!(
+            orientation(&a, &b, &Point::new(2.0, 0.0)),
+            Orientation::Collinear
+        );
+        assert_eq!(orientation(&a, &a, &b), Orientation::Collinear);
+    }
+
+    #[test]
+    fn collinear_tolerance_scales() {
+        // nearly on the line, relative to the lengths
+        let a = Point::new(1000.0, 1000.0);
+        let b = Point::new(3000.0, 3000.0);
+        let c = Point::new(2000.0, 2000.0001);
+
+        assert_eq!(orientation(&a, &b, &c), Orientation::Collinear);
+        assert_eq!(
+            orientation(
+                &Point::new(0.0, 0.0),
+                &Point::new(0.001, 0.0),
+                &Point::new(0.001, 0.001)
+            ),
+            Orientation::CounterClockwise
+        );
+    }
+}
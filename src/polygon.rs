@@ -0,0 +1,276 @@
+use crate::{point::cross, types::PointIdx, Point, Triangle, TriangulatorError};
+
+/// crossing number test, true if point is inside the polygon given by indices into points
+pub(crate) fn point_in_polygon(point: &Point, polygon: &[PointIdx], points: &[Point]) -> bool {
+    let mut inside = false;
+
+    for i in 0..polygon.len() {
+        let a = &points[polygon[i] as usize];
+        let b = &points[polygon[(i + 1) % polygon.len()] as usize];
+
+        // does a horizontal ray from point towards +x cross edge a-b
+        if (a.y > point.y) != (b.y > point.y) {
+            let x = a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x);
+            if point.x < x {
+                inside = !inside;
+            }
+        }
+    }
+
+    inside
+}
+
+/// positive for counter clockwise polygons
+pub(crate) fn signed_area(polygon: &[PointIdx], points: &[Point]) -> f32 {
+    let mut area = 0.0;
+    for i in 0..polygon.len() {
+        let a = &points[polygon[i] as usize];
+        let b = &points[polygon[(i + 1) % polygon.len()] as usize];
+        area += a.x * b.y - b.x * a.y;
+    }
+    0.5 * area
+}
+
+/// the direction a polygon's boundary goes around, with y pointing up
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Winding {
+    CounterClockwise,
+    Clockwise,
+    /// no area, eg fewer than 3 vertices, all of them collinear or a NaN coordinate
+    Degenerate,
+}
+
+/// the winding of the polygon with vertices boundary, by the sign of its area.
+/// degenerate if the area is within f32::EPSILON times the squared size of its bounding box
+pub fn polygon_winding(boundary: &[Point]) -> Winding {
+    let (mut min_x, mut min_y) = (f32::MAX, f32::MAX);
+    let (mut max_x, mut max_y) = (f32::MIN, f32::MIN);
+    let mut area = 0.0;
+    for (i, a) in boundary.iter().enumerate() {
+        let b = &boundary[(i + 1) % boundary.len()];
+        area += a.x * b.y - b.x * a.y;
+        min_x = min_x.min(a.x);
+        min_y = min_y.min(a.y);
+        max_x = max_x.max(a.x);
+        max_y = max_y.max(a.y);
+    }
+    let size = (max_x - min_x).max(max_y - min_y);
+
+    if area.is_nan() || 0.5 * area.abs() <= f32::EPSILON * size * size {
+        Winding::Degenerate
+    } else if area > 0.0 {
+        Winding::CounterClockwise
+    } else {
+        Winding::Clockwise
+    }
+}
+
+/// ear clipping of a simple polygon, clockwise or counter clockwise, giving counter clockwise triangles.
+/// all vertices are used, so vertices collinear with their neighbours may give zero area triangles
+pub(crate) fn triangulate_polygon(boundary: &[Point]) -> Result<Vec<Triangle>, TriangulatorError> {
+    if let Some(i) = boundary.iter().position(|p| p.x.is_nan() || p.y.is_nan()) {
+        return Err(TriangulatorError::NANInInput(i));
+    }
+
+    let mut remaining = (0..boundary.len() as PointIdx).collect::<Vec<_>>();
+    match polygon_winding(boundary) {
+        Winding::CounterClockwise => {}
+        Winding::Clockwise => remaining.reverse(),
+        Winding::Degenerate => return Err(TriangulatorError::NotSimplePolygon),
+    }
+
+    let mut triangles = Vec::with_capacity(boundary.len() - 2);
+    while remaining.len() > 3 {
+        let n = remaining.len();
+        let corner = |i: usize| {
+            (
+                remaining[(i + n - 1) % n],
+                remaining[i],
+                remaining[(i + 1) % n],
+            )
+        };
+
+        let ear = (0..n).find(|i| {
+            let (prev, curr, next) = corner(*i);
+            is_ear(prev, curr, next, &remaining, boundary)
+        });
+        match ear {
+            Some(i) => {
+                let (prev, curr, next) = corner(i);
+                triangles.push(Triangle::new(prev, curr, next));
+                remaining.remove(i);
+            }
+            None => {
+                // collinear vertices are never ears, clip one as a zero area ear once they are all that is left.
+                // leaving it out instead would put it on the side of a triangle without being one of its corners
+                let straight = (0..n).find(|i| {
+                    let (prev, curr, next) = corner(*i);
+                    triangle_area(prev, curr, next, boundary) == 0.0
+                });
+                match straight {
+                    Some(i) => {
+                        let (prev, curr, next) = corner(i);
+                        triangles.push(Triangle::new(prev, curr, next));
+                        remaining.remove(i);
+                    }
+                    None => return Err(TriangulatorError::NotSimplePolygon),
+                }
+            }
+        }
+    }
+
+    triangles.push(Triangle::new(remaining[0], remaining[1], remaining[2]));
+    Ok(triangles)
+}
+
+// twice the signed area, positive if counter clockwise
+fn triangle_area(a: PointIdx, b: PointIdx, c: PointIdx, points: &[Point]) -> f32 {
+    cross(
+        &points[b as usize],
+        &points[c as usize],
+        &points[a as usize],
+    )
+}
+
+// convex corner with no other vertex inside or on it
+fn is_ear(
+    prev: PointIdx,
+    curr: PointIdx,
+    next: PointIdx,
+    remaining: &[PointIdx],
+    points: &[Point],
+) -> bool {
+    if triangle_area(prev, curr, next, points) <= 0.0 {
+        return false;
+    }
+
+    let (a, b, c) = (
+        &points[prev as usize],
+        &points[curr as usize],
+        &points[next as usize],
+    );
+    remaining.iter().all(|idx| {
+        let p = &points[*idx as usize];
+        let is_corner = [prev, curr, next].contains(idx)
+            || (p.x == a.x && p.y == a.y)
+            || (p.x == b.x && p.y == b.y)
+            || (p.x == c.x && p.y == c.y);
+        is_corner || !(cross(b, p, a) >= 0.0 && cross(c, p, b) >= 0.0 && cross(a, p, c) >= 0.0)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inside_and_outside_l_shape() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 1.0),
+            Point::new(1.0, 1.0),
+            Point::new(1.0, 2.0),
+            Point::new(0.0, 2.0),
+        ];
+        let polygon = [0, 1, 2, 3, 4, 5];
+
+        assert!(point_in_polygon(&Point::new(0.5, 0.5), &polygon, &points));
+        assert!(point_in_polygon(&Point::new(1.5, 0.5), &polygon, &points));
+        assert!(point_in_polygon(&Point::new(0.5, 1.5), &polygon, &points));
+        assert!(!point_in_polygon(&Point::new(1.5, 1.5), &polygon, &points));
+        assert!(!point_in_polygon(&Point::new(-0.5, 0.5), &polygon, &points));
+    }
+
+    #[test]
+    fn ear_clipping_stays_inside_l_shape() {
+        let l_shape = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 1.0),
+            Point::new(1.0, 1.0),
+            Point::new(1.0, 2.0),
+            Point::new(0.0, 2.0),
+        ];
+        let polygon = (0..l_shape.len() as PointIdx).collect::<Vec<_>>();
+        let reversed = l_shape.iter().rev().copied().collect::<Vec<_>>();
+
+        for points in [&l_shape[..], &reversed[..]] {
+            let triangles = triangulate_polygon(points).unwrap();
+
+            assert_eq!(triangles.len(), points.len() - 2);
+            let area: f32 = triangles.iter().map(|t| t.area(points)).sum();
+            assert!((area - 3.0).abs() < 1e-5);
+            for tri in triangles.iter() {
+                assert!(triangle_area(tri.index0, tri.index1, tri.index2, points) > 0.0);
+                let centroid = tri.centroid(points);
+                assert!(point_in_polygon(&centroid, &polygon, points));
+            }
+        }
+    }
+
+    #[test]
+    fn ear_clipping_with_collinear_vertex() {
+        // square with an extra vertex in the middle of the bottom edge
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+        ];
+        let triangles = triangulate_polygon(&points).unwrap();
+        let area: f32 = triangles.iter().map(|t| t.area(&points)).sum();
+        assert!((area - 4.0).abs() < 1e-5);
+        assert_eq!(triangles.len(), 3);
+    }
+
+    #[test]
+    fn ear_clipping_keeps_rounded_collinear_vertices() {
+        // the vertices between the corners of the quadrilateral are collinear up to rounding
+        let points = [
+            Point::new(1.9019923, 1.1968406),
+            Point::new(0.5900053, 2.2242408),
+            Point::new(-0.7219817, 3.2516408),
+            Point::new(-1.9006543, 0.34566522),
+            Point::new(-3.079327, -2.5603104),
+            Point::new(-1.8339972, -1.6210226),
+            Point::new(-0.5886674, -0.6817348),
+            Point::new(0.65666246, 0.2575531),
+        ];
+        let triangles = triangulate_polygon(&points).unwrap();
+
+        assert_eq!(triangles.len(), points.len() - 2);
+        for vertex in 0..points.len() as PointIdx {
+            assert!(triangles
+                .iter()
+                .any(|t| [t.index0, t.index1, t.index2].contains(&vertex)));
+        }
+    }
+
+    #[test]
+    fn winding() {
+        let square = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(0.0, 1.0),
+        ];
+        let reversed = square.iter().rev().copied().collect::<Vec<_>>();
+        let line = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(2.0, 2.0),
+            Point::new(1.0, 1.0),
+        ];
+
+        assert_eq!(polygon_winding(&square), Winding::CounterClockwise);
+        assert_eq!(polygon_winding(&reversed), Winding::Clockwise);
+        assert_eq!(polygon_winding(&line), Winding::Degenerate);
+        assert_eq!(polygon_winding(&square[..2]), Winding::Degenerate);
+        assert_eq!(
+            triangulate_polygon(&line),
+            Err(TriangulatorError::NotSimplePolygon)
+        );
+    }
+}
@@ -0,0 +1,216 @@
+use crate::{
+    polygon::{point_in_polygon, polygon_winding, Winding},
+    triangulate,
+    types::PointIdx,
+    Circle, Edge, Point, Triangle, TriangulatorError,
+};
+
+/// triangulation of a planar straight line graph, where the input segments are edges of the triangles
+#[derive(Debug, Clone)]
+pub struct ConformingTriangulation {
+    /// the input points, followed by the points added on segments
+    pub points: Vec<Point>,
+    pub triangles: Vec<Triangle>,
+    /// the input segments, split where points were added on them
+    pub segments: Vec<Edge>,
+}
+
+/// splits segments at their midpoint while any point is inside their diametral circle, then triangulates.
+/// a segment with no points in its diametral circle is a delaunay edge, so all (split) segments end up in the triangulation
+pub(crate) fn triangulate_pslg(
+    points: &[Point],
+    segments: &[(PointIdx, PointIdx)],
+) -> Result<ConformingTriangulation, TriangulatorError> {
+    for (i, (a, b)) in segments.iter().enumerate() {
+        if *a as usize >= points.len() || *b as usize >= points.len() || a == b {
+            return Err(TriangulatorError::InvalidSegment(i));
+        }
+    }
+
+    let mut points = points.to_vec();
+    let mut done = Vec::with_capacity(segments.len());
+    let mut to_check = segments
+        .iter()
+        .map(|(a, b)| Edge::new(*a, *b))
+        .collect::<Vec<_>>();
+
+    while let Some(segment) = to_check.pop() {
+        let a = points[segment.index_0 as usize];
+        let b = points[segment.index_1 as usize];
+        let circle = Circle::from_diameter(&a, &b);
+        let midpoint = circle.pos;
+
+        let encroached = points.iter().enumerate().any(|(idx, p)| {
+            idx as PointIdx != segment.index_0
+                && idx as PointIdx != segment.index_1
+                && circle.contains(p)
+        });
+
+        // stop splitting when the midpoint can't be told apart from the ends in f32
+        let splittable =
+            (midpoint.x != a.x || midpoint.y != a.y) && (midpoint.x != b.x || midpoint.y != b.y);
+
+        if encroached && splittable {
+            let mid_idx = points.len() as PointIdx;
+            points.push(midpoint);
+            to_check.push(Edge::new(segment.index_0, mid_idx));
+            to_check.push(Edge::new(mid_idx, segment.index_1));
+            // the new point may encroach segments that were fine before
+            to_check.append(&mut done);
+        } else {
+            done.push(segment);
+        }
+    }
+
+    let triangles = triangulate(&points)?;
+    done.sort_unstable();
+
+    Ok(ConformingTriangulation {
+        points,
+        triangles,
+        segments: done,
+    })
+}
+
+/// triangulates the region inside outer and outside all holes. the boundaries are conforming segments,
+/// then triangles are kept by their centroid, like clip_to_polygon
+pub(crate) fn triangulate_with_holes(
+    outer: &[Point],
+    holes: &[Vec<Point>],
+) -> Result<ConformingTriangulation, TriangulatorError> {
+    let mut points = Vec::with_capacity(outer.len() + holes.iter().map(Vec::len).sum::<usize>());
+    let mut loops = Vec::with_capacity(1 + holes.len());
+    for boundary in std::iter::once(outer).chain(holes.iter().map(|hole| hole.as_slice())) {
+        if boundary.len() < 3 {
+            return Err(TriangulatorError::TooFewPoints);
+        }
+        if polygon_winding(boundary) == Winding::Degenerate {
+            return Err(TriangulatorError::NotSimplePolygon);
+        }
+        let start = points.len();
+        points.extend_from_slice(boundary);
+        loops.push(
+            (start..start + boundary.len())
+                .map(|i| i as PointIdx)
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    let segments = loops
+        .iter()
+        .flat_map(|indices| {
+            (0..indices.len()).map(move |i| (indices[i], indices[(i + 1) % indices.len()]))
+        })
+        .collect::<Vec<_>>();
+
+    let mut res = triangulate_pslg(&points, &segments)?;
+
+    // added points are on the segments, so the loops of the input points still describe the region
+    let all_points = &res.points;
+    res.triangles.retain(|tri| {
+        let centroid = tri.centroid(all_points);
+        point_in_polygon(&centroid, &loops[0], &points)
+            && !loops[1..]
+                .iter()
+                .any(|hole| point_in_polygon(&centroid, hole, &points))
+    });
+
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{boundary::boundary_edges, types::HashSet};
+
+    fn triangle_edges(triangles: &[Triangle]) -> HashSet<Edge> {
+        triangles
+            .iter()
+            .flat_map(|tri| {
+                [
+                    Edge::new(tri.index0, tri.index1),
+                    Edge::new(tri.index1, tri.index2),
+                    Edge::new(tri.index2, tri.index0),
+                ]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn encroached_segment_is_split() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(5.0, 8.0),
+            Point::new(5.0, 1.0),
+        ];
+
+        let res = triangulate_pslg(&points, &[(0, 1)]).unwrap();
+
+        assert!(res.points.len() > points.len());
+        assert!(res.points[points.len()..].iter().all(|p| p.y == 0.0));
+        assert!(res.segments.len() > 1);
+
+        let edges = triangle_edges(&res.triangles);
+        assert!(res.segments.iter().all(|segment| edges.contains(segment)));
+        assert_eq!(boundary_edges(&res.triangles).len(), 2 + res.segments.len());
+    }
+
+    #[test]
+    fn free_segment_is_kept() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(5.0, 8.0),
+        ];
+
+        let res = triangulate_pslg(&points, &[(0, 1)]).unwrap();
+
+        assert_eq!(res.points.len(), points.len());
+        assert_eq!(res.segments, [Edge::new(0, 1)]);
+    }
+
+    #[test]
+    fn invalid_segment() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(5.0, 8.0),
+        ];
+
+        let res = triangulate_pslg(&points, &[(0, 1), (1, 3)]);
+
+        assert_eq!(res.unwrap_err(), TriangulatorError::InvalidSegment(1));
+    }
+
+    #[test]
+    fn square_with_hole() {
+        let outer = [
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(10.0, 10.0),
+            Point::new(0.0, 10.0),
+        ];
+        let hole = vec![
+            Point::new(4.0, 4.0),
+            Point::new(6.0, 4.0),
+            Point::new(6.0, 6.0),
+            Point::new(4.0, 6.0),
+        ];
+
+        let res = triangulate_with_holes(&outer, &[hole]).unwrap();
+
+        let in_hole = |p: &Point| p.x > 4.0 && p.x < 6.0 && p.y > 4.0 && p.y < 6.0;
+        assert!(res
+            .triangles
+            .iter()
+            .all(|tri| !in_hole(&tri.centroid(&res.points))));
+
+        let area = res
+            .triangles
+            .iter()
+            .map(|tri| tri.area(&res.points))
+            .sum::<f32>();
+        assert!((area - 96.0).abs() < 1e-3, "{}", area);
+    }
+}
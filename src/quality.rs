@@ -0,0 +1,50 @@
+use crate::{Point, Triangle};
+
+/// a color per triangle for its smallest angle, from red for slivers to green for equilateral triangles,
+/// eg to color an svg or png export by quality. degenerate triangles are red
+pub fn quality_colors(points: &[Point], triangles: &[Triangle]) -> Vec<[u8; 3]> {
+    let equilateral = 60.0_f32.to_radians();
+    triangles
+        .iter()
+        .map(|tri| {
+            let angle = tri.min_angle(points);
+            let quality = if angle.is_nan() {
+                0.0
+            } else {
+                (angle / equilateral).clamp(0.0, 1.0)
+            };
+            [
+                (255.0 * (1.0 - quality)).round() as u8,
+                (255.0 * quality).round() as u8,
+                0,
+            ]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equilateral_green_sliver_red() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.5, 0.75_f32.sqrt()),
+            Point::new(0.5, 0.001),
+            Point::new(2.0, 0.0),
+        ];
+        let triangles = [
+            Triangle::new(0, 1, 2),
+            Triangle::new(0, 1, 3),
+            Triangle::new(0, 1, 4),
+        ];
+
+        let colors = quality_colors(&points, &triangles);
+
+        assert_eq!(colors[0], [0, 255, 0]);
+        assert!(colors[1][0] >= 250 && colors[1][1] <= 5);
+        assert_eq!(colors[2], [255, 0, 0]);
+    }
+}
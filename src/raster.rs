@@ -0,0 +1,173 @@
+use image::{Rgba, RgbaImage};
+
+use crate::{point::cross, triangle::bounding_box, Point, Triangle};
+
+/// colors used by rasterize_with. None skips filling or outlining
+#[derive(Clone, Debug)]
+pub struct RasterStyle {
+    pub background: Rgba<u8>,
+    pub fill: Option<Rgba<u8>>,
+    pub outline: Option<Rgba<u8>>,
+}
+
+impl Default for RasterStyle {
+    fn default() -> Self {
+        Self {
+            background: Rgba([255, 255, 255, 255]),
+            fill: Some(Rgba([200, 220, 255, 255])),
+            outline: Some(Rgba([0, 0, 0, 255])),
+        }
+    }
+}
+
+/// draws the triangles, filled and outlined, scaled to fit a width x height image. y points up
+pub fn rasterize(points: &[Point], triangles: &[Triangle], width: u32, height: u32) -> RgbaImage {
+    rasterize_with(points, triangles, width, height, &RasterStyle::default())
+}
+
+/// same as rasterize, with custom colors, eg wireframe only
+pub fn rasterize_with(
+    points: &[Point],
+    triangles: &[Triangle],
+    width: u32,
+    height: u32,
+    style: &RasterStyle,
+) -> RgbaImage {
+    let mut image = RgbaImage::from_pixel(width, height, style.background);
+    if width == 0 || height == 0 || triangles.is_empty() {
+        return image;
+    }
+
+    let to_pixel = fit_to_image(points, triangles, width, height);
+
+    if let Some(fill) = style.fill {
+        for tri in triangles {
+            let a = to_pixel(&points[tri.index0 as usize]);
+            let b = to_pixel(&points[tri.index1 as usize]);
+            let c = to_pixel(&points[tri.index2 as usize]);
+            fill_triangle(&mut image, [a, b, c], fill);
+        }
+    }
+
+    if let Some(outline) = style.outline {
+        for tri in triangles {
+            let a = to_pixel(&points[tri.index0 as usize]);
+            let b = to_pixel(&points[tri.index1 as usize]);
+            let c = to_pixel(&points[tri.index2 as usize]);
+            draw_line(&mut image, a, b, outline);
+            draw_line(&mut image, b, c, outline);
+            draw_line(&mut image, c, a, outline);
+        }
+    }
+
+    image
+}
+
+/// maps points to pixel coordinates, keeping the aspect ratio and flipping y
+fn fit_to_image(
+    points: &[Point],
+    triangles: &[Triangle],
+    width: u32,
+    height: u32,
+) -> impl Fn(&Point) -> Point {
+    // only called with triangles
+    let (min, max) = bounding_box(triangles, points).unwrap();
+
+    let usable_w = (width - 1) as f32;
+    let usable_h = (height - 1) as f32;
+    let extent_x = (max.x - min.x).max(f32::EPSILON);
+    let extent_y = (max.y - min.y).max(f32::EPSILON);
+    let scale = (usable_w / extent_x).min(usable_h / extent_y);
+    let offset_x = 0.5 * (usable_w - extent_x * scale);
+    let offset_y = 0.5 * (usable_h - extent_y * scale);
+
+    move |p: &Point| {
+        Point::new(
+            offset_x + (p.x - min.x) * scale,
+            usable_h - offset_y - (p.y - min.y) * scale,
+        )
+    }
+}
+
+fn fill_triangle(image: &mut RgbaImage, corners: [Point; 3], color: Rgba<u8>) {
+    let [a, b, c] = corners;
+    let area = cross(&b, &c, &a);
+    if area == 0.0 {
+        return;
+    }
+
+    let x0 = a.x.min(b.x).min(c.x).floor().max(0.0) as u32;
+    let y0 = a.y.min(b.y).min(c.y).floor().max(0.0) as u32;
+    let x1 = (a.x.max(b.x).max(c.x).ceil() as u32).min(image.width() - 1);
+    let y1 = (a.y.max(b.y).max(c.y).ceil() as u32).min(image.height() - 1);
+
+    for y in y0..=y1 {
+        for x in x0..=x1 {
+            let p = Point::new(x as f32, y as f32);
+            let w0 = cross(&b, &c, &p) / area;
+            let w1 = cross(&c, &a, &p) / area;
+            let w2 = cross(&a, &b, &p) / area;
+            if w0 >= 0.0 && w1 >= 0.0 && w2 >= 0.0 {
+                image.put_pixel(x, y, color);
+            }
+        }
+    }
+}
+
+fn draw_line(image: &mut RgbaImage, from: Point, to: Point, color: Rgba<u8>) {
+    let steps = (to.x - from.x)
+        .abs()
+        .max((to.y - from.y).abs())
+        .ceil()
+        .max(1.0) as u32;
+    for step in 0..=steps {
+        let t = step as f32 / steps as f32;
+        let x = (from.x + (to.x - from.x) * t).round();
+        let y = (from.y + (to.y - from.y) * t).round();
+        if x >= 0.0 && y >= 0.0 && (x as u32) < image.width() && (y as u32) < image.height() {
+            image.put_pixel(x as u32, y as u32, color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_triangle_covers_expected_region() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.0, 1.0),
+        ];
+        let triangles = [Triangle::new(0, 1, 2)];
+        let style = RasterStyle::default();
+
+        let image = rasterize(&points, &triangles, 64, 64);
+
+        // lower left half is the triangle, y flipped in the image
+        assert_eq!(*image.get_pixel(10, 50), style.fill.unwrap());
+        assert_eq!(*image.get_pixel(50, 10), style.background);
+        assert_eq!(*image.get_pixel(0, 63), style.outline.unwrap());
+    }
+
+    #[test]
+    fn wireframe_leaves_interior_empty() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.0, 1.0),
+        ];
+        let triangles = [Triangle::new(0, 1, 2)];
+        let style = RasterStyle {
+            fill: None,
+            ..RasterStyle::default()
+        };
+
+        let image = rasterize_with(&points, &triangles, 64, 64, &style);
+
+        assert_eq!(*image.get_pixel(10, 50), style.background);
+        assert!(image.pixels().any(|p| *p == style.outline.unwrap()));
+    }
+}
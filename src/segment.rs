@@ -0,0 +1,139 @@
+use crate::{
+    point::{cross, orientation},
+    Orientation, Point,
+};
+
+// tolerance on the segment parameters, so that touching at an endpoint counts despite rounding
+const PARAM_EPSILON: f32 = 1e-6;
+
+/// the point where segment a0-a1 meets segment b0-b1, None if they don't.
+/// segments touching at an endpoint, or an endpoint touching the other segment, meet at that point.
+/// collinear overlapping segments give the point of the overlap closest to a0.
+/// parallel, non collinear segments never meet
+pub fn segments_intersect(a0: &Point, a1: &Point, b0: &Point, b1: &Point) -> Option<Point> {
+    let r = *a1 - a0;
+    let s = *b1 - b0;
+
+    // a degenerate segment is a point, which is on the other segment or not
+    if r.x == 0.0 && r.y == 0.0 {
+        if s.x == 0.0 && s.y == 0.0 {
+            return if a0.x == b0.x && a0.y == b0.y {
+                Some(*a0)
+            } else {
+                None
+            };
+        }
+        return segments_intersect(b0, b1, a0, a1);
+    }
+
+    let r_len_sqr = r.x * r.x + r.y * r.y;
+    let along_a = |p: &Point| ((p.x - a0.x) * r.x + (p.y - a0.y) * r.y) / r_len_sqr;
+
+    if orientation(a0, a1, b0) == Orientation::Collinear
+        && orientation(a0, a1, b1) == Orientation::Collinear
+    {
+        let (t0, t1) = (along_a(b0), along_a(b1));
+        let lo = t0.min(t1).max(0.0);
+        let hi = t0.max(t1).min(1.0);
+        return if lo <= hi + PARAM_EPSILON {
+            Some(*a0 + &(r * lo))
+        } else {
+            None
+        };
+    }
+
+    let denom = r.x * s.y - r.y * s.x;
+    if denom == 0.0 {
+        return None;
+    }
+
+    // a0 + t r = b0 + u s
+    let origin = Point::new(0.0, 0.0);
+    let qp = *b0 - a0;
+    let t = cross(&qp, &s, &origin) / denom;
+    let u = cross(&qp, &r, &origin) / denom;
+    let in_range = |v: f32| (-PARAM_EPSILON..=1.0 + PARAM_EPSILON).contains(&v);
+    if in_range(t) && in_range(u) {
+        Some(*a0 + &(r * t.clamp(0.0, 1.0)))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(p: Option<Point>, x: f32, y: f32) -> bool {
+        p.is_some_and(|p| (p.x - x).abs() < 1e-5 && (p.y - y).abs() < 1e-5)
+    }
+
+    #[test]
+    fn crossing() {
+        let p = segments_intersect(
+            &Point::new(0.0, 0.0),
+            &Point::new(2.0, 2.0),
+            &Point::new(0.0, 2.0),
+            &Point::new(2.0, 0.0),
+        );
+        assert!(close(p, 1.0, 1.0));
+
+        let miss = segments_intersect(
+            &Point::new(0.0, 0.0),
+            &Point::new(1.0, 1.0),
+            &Point::new(3.0, 0.0),
+            &Point::new(2.0, 1.0),
+        );
+        assert!(miss.is_none());
+    }
+
+    #[test]
+    fn parallel() {
+        let p = segments_intersect(
+            &Point::new(0.0, 0.0),
+            &Point::new(2.0, 0.0),
+            &Point::new(0.0, 1.0),
+            &Point::new(2.0, 1.0),
+        );
+        assert!(p.is_none());
+    }
+
+    #[test]
+    fn touching() {
+        // shared endpoint
+        let p = segments_intersect(
+            &Point::new(0.0, 0.0),
+            &Point::new(1.0, 0.0),
+            &Point::new(1.0, 0.0),
+            &Point::new(1.0, 1.0),
+        );
+        assert!(close(p, 1.0, 0.0));
+
+        // t junction
+        let p = segments_intersect(
+            &Point::new(0.0, 0.0),
+            &Point::new(2.0, 0.0),
+            &Point::new(0.7, 0.0),
+            &Point::new(0.7, 1.0),
+        );
+        assert!(close(p, 0.7, 0.0));
+    }
+
+    #[test]
+    fn collinear() {
+        let a0 = Point::new(0.0, 0.0);
+        let a1 = Point::new(2.0, 2.0);
+
+        let overlap = segments_intersect(&a0, &a1, &Point::new(3.0, 3.0), &Point::new(1.0, 1.0));
+        assert!(close(overlap, 1.0, 1.0));
+
+        let inside = segments_intersect(&a0, &a1, &Point::new(-1.0, -1.0), &Point::new(3.0, 3.0));
+        assert!(close(inside, 0.0, 0.0));
+
+        let apart = segments_intersect(&a0, &a1, &Point::new(3.0, 3.0), &Point::new(4.0, 4.0));
+        assert!(apart.is_none());
+
+        let point = segments_intersect(&a0, &a0, &Point::new(-1.0, -1.0), &a1);
+        assert!(close(point, 0.0, 0.0));
+    }
+}
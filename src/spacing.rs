@@ -0,0 +1,125 @@
+use crate::{
+    types::{HashSet, PointIdx},
+    Edge, Point, Triangulator, TriangulatorError,
+};
+
+/// distance from each point to its nearest other point.
+/// the nearest neighbour of a point always shares a delaunay edge with it, so only the triangle edges are measured.
+/// duplicated points get 0. with fewer than 3 points, or all of them collinear, gives the triangulation error
+pub fn nearest_spacing(points: &[Point]) -> Result<Vec<f32>, TriangulatorError> {
+    let mut triangulator = Triangulator::from_points(points)?;
+    while triangulator.do_step(points) {}
+
+    let mut spacing = vec![f32::INFINITY; points.len()];
+    for tri in triangulator.iter() {
+        for (a, b) in [
+            (tri.index0, tri.index1),
+            (tri.index1, tri.index2),
+            (tri.index2, tri.index0),
+        ] {
+            let d = points[a as usize].distance(&points[b as usize]);
+            spacing[a as usize] = spacing[a as usize].min(d);
+            spacing[b as usize] = spacing[b as usize].min(d);
+        }
+    }
+    for (skipped, vertex) in triangulator.merged_points() {
+        spacing[*skipped as usize] = 0.0;
+        spacing[*vertex as usize] = 0.0;
+    }
+
+    Ok(spacing)
+}
+
+/// indices of isolated points, whose mean delaunay edge length is more than factor times the median edge length of the triangulation.
+/// points that aren't part of the triangulation, ie duplicates, are never flagged.
+/// the long edges of an isolated point are shared with its neighbours, which can be flagged as well if they have few other edges
+pub fn filter_outliers(points: &[Point], factor: f32) -> Result<Vec<PointIdx>, TriangulatorError> {
+    let mut triangulator = Triangulator::from_points(points)?;
+    while triangulator.do_step(points) {}
+
+    let mut edges = HashSet::default();
+    for tri in triangulator.iter() {
+        edges.insert(Edge::new(tri.index0, tri.index1));
+        edges.insert(Edge::new(tri.index1, tri.index2));
+        edges.insert(Edge::new(tri.index2, tri.index0));
+    }
+
+    let mut length_sum = vec![0.0; points.len()];
+    let mut edge_count = vec![0; points.len()];
+    let mut lengths = Vec::with_capacity(edges.len());
+    for edge in edges.iter() {
+        let (a, b) = (edge.index_0 as usize, edge.index_1 as usize);
+        let length = points[a].distance(&points[b]);
+        lengths.push(length);
+        for p in [a, b] {
+            length_sum[p] += length;
+            edge_count[p] += 1;
+        }
+    }
+    lengths.sort_unstable_by(f32::total_cmp);
+    let median = lengths[lengths.len() / 2];
+
+    Ok((0..points.len())
+        .filter(|&p| edge_count[p] > 0 && length_sum[p] / edge_count[p] as f32 > factor * median)
+        .map(|p| p as PointIdx)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn same_as_brute_force() {
+        let mut rng = StdRng::seed_from_u64(12);
+        let points = (0..30)
+            .map(|_| Point::new(rng.gen_range(0.0..10.0), rng.gen_range(0.0..10.0)))
+            .collect::<Vec<_>>();
+
+        let spacing = nearest_spacing(&points).unwrap();
+
+        for (i, p) in points.iter().enumerate() {
+            let brute = points
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, q)| (*p - q).x.hypot((*p - q).y))
+                .fold(f32::INFINITY, f32::min);
+            assert_eq!(spacing[i], brute);
+        }
+    }
+
+    #[test]
+    fn duplicates_have_zero_spacing() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(1.0, 2.0),
+            Point::new(1.0, 0.5),
+            Point::new(1.0, 0.5),
+        ];
+
+        let spacing = nearest_spacing(&points).unwrap();
+
+        assert_eq!(spacing[3], 0.0);
+        assert_eq!(spacing[4], 0.0);
+        assert!(spacing[0] > 0.0);
+    }
+
+    #[test]
+    fn isolated_point_is_outlier() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut points = (0..100)
+            .map(|_| Point::new(rng.gen_range(0.0..1.0), rng.gen_range(0.0..1.0)))
+            .collect::<Vec<_>>();
+        assert!(filter_outliers(&points, 5.0).unwrap().is_empty());
+
+        points.push(Point::new(20.0, 20.0));
+        let outliers = filter_outliers(&points, 5.0).unwrap();
+
+        assert!(outliers.contains(&100));
+        // only hull points next to the outlier are dragged along
+        assert!(outliers.len() < 15, "{:?}", outliers);
+    }
+}
@@ -0,0 +1,185 @@
+use crate::{
+    delaunay_inc::DelaunayIncremental, types::PointIdx, Point, Triangle, TriangulatorError,
+};
+
+/// triangulation of points arriving one at a time, eg from a sensor, which are kept by the triangulator.
+/// each point is added as it is pushed, inside the triangles or outside of them, so the mesh is always up to date.
+/// until three points that aren't collinear have been pushed there are no triangles
+#[derive(Default, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StreamingTriangulator {
+    points: Vec<Point>,
+    triangulator: DelaunayIncremental,
+}
+
+impl StreamingTriangulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// adds p to the triangulation. a point at the position of an earlier one is kept in points but left out of
+    /// the triangles, see merged_points. a point that gives an error, eg a NAN, isn't kept and the triangles are
+    /// unchanged. except for FlipLimitExceeded outside of the triangles, which can't be undone: the point is then
+    /// kept, in triangles that are valid but maybe not delaunay
+    pub fn push(&mut self, p: Point) -> Result<(), TriangulatorError> {
+        let point_idx = self.points.len() as PointIdx;
+        if p.x.is_nan() || p.y.is_nan() {
+            return Err(TriangulatorError::NANInInput(point_idx as usize));
+        }
+        self.points.push(p);
+
+        let res = if !self.triangulator.get_triangles().is_empty() {
+            match self.triangulator.insert_point(point_idx, &self.points) {
+                Err(TriangulatorError::PointOutsideOfHull) => {
+                    match self.triangulator.extend_hull(point_idx, &self.points) {
+                        Err(TriangulatorError::FlipLimitExceeded) => {
+                            return Err(TriangulatorError::FlipLimitExceeded)
+                        }
+                        res => res,
+                    }
+                }
+                Err(err) => {
+                    // the point was added, as a rejected coincident point or with flipping giving up
+                    self.triangulator.undo_insert();
+                    Err(err)
+                }
+                Ok(()) => Ok(()),
+            }
+        } else {
+            // the first triangles, from all points so far, once they aren't all collinear
+            let res = match self.triangulator.initial_triangulation(&self.points) {
+                Err(TriangulatorError::Collinear) => Ok(()),
+                res => res
+                    .map(|_| ())
+                    .and_then(|_| self.triangulator.add_all(&self.points)),
+            };
+            if res.is_err() {
+                self.triangulator.reset();
+            }
+            res
+        };
+
+        if res.is_err() {
+            self.points.pop();
+        }
+        res
+    }
+
+    pub fn triangles(&self) -> &[Triangle] {
+        self.triangulator.get_triangles()
+    }
+
+    /// all pushed points, which the triangles index
+    pub fn points(&self) -> &[Point] {
+        &self.points
+    }
+
+    /// pairs of (left out point, point at the same position in the triangles)
+    pub fn merged_points(&self) -> &[(PointIdx, PointIdx)] {
+        &self.triangulator.merged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FlipCriterion;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn sorted(triangles: &[Triangle]) -> Vec<[PointIdx; 3]> {
+        let mut sorted = triangles
+            .iter()
+            .map(|tri| {
+                let mut indices = [tri.index0, tri.index1, tri.index2];
+                indices.sort_unstable();
+                indices
+            })
+            .collect::<Vec<_>>();
+        sorted.sort_unstable();
+        sorted
+    }
+
+    #[test]
+    fn same_as_batch() {
+        let mut rng = StdRng::seed_from_u64(17);
+        let points = (0..150)
+            .map(|_| Point::new(rng.gen_range(0.0..10.0), rng.gen_range(0.0..10.0)))
+            .collect::<Vec<_>>();
+
+        let mut streaming = StreamingTriangulator::new();
+        for p in points.iter() {
+            streaming.push(*p).unwrap();
+        }
+
+        assert_eq!(
+            sorted(streaming.triangles()),
+            sorted(&crate::triangulate(&points).unwrap())
+        );
+    }
+
+    #[test]
+    fn starts_when_not_collinear() {
+        let mut streaming = StreamingTriangulator::new();
+        for p in [
+            Point::new(0.0, 0.0),
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(2.0, 2.0),
+        ] {
+            streaming.push(p).unwrap();
+            assert!(streaming.triangles().is_empty());
+        }
+
+        streaming.push(Point::new(2.0, 0.0)).unwrap();
+        assert!(!streaming.triangles().is_empty());
+
+        // on the boundary, then outside of it
+        streaming.push(Point::new(1.0, 0.0)).unwrap();
+        streaming.push(Point::new(1.0, -1.0)).unwrap();
+        let area = streaming
+            .triangles()
+            .iter()
+            .map(|tri| tri.area(streaming.points()))
+            .sum::<f32>();
+        assert!((area - 3.0).abs() < 1e-5, "{}", area);
+
+        assert!(streaming.push(Point::new(f32::NAN, 0.0)).is_err());
+        assert_eq!(streaming.points().len(), 7);
+    }
+
+    #[test]
+    fn failed_push_leaves_triangles_unchanged() {
+        let mut streaming = StreamingTriangulator::new();
+        for p in [
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(0.0, 4.0),
+            Point::new(4.0, 4.5),
+        ] {
+            streaming.push(p).unwrap();
+        }
+        let triangles = streaming.triangles().to_vec();
+
+        streaming.triangulator.options.reject_coincident = true;
+        assert_eq!(
+            streaming.push(Point::new(4.0, 0.0)),
+            Err(TriangulatorError::CoincidentPoints(1, 4))
+        );
+        assert!(streaming.merged_points().is_empty());
+
+        // splitting the triangle next to the diagonal gives slivers, which need flipping
+        streaming.triangulator.options.flip_criterion = FlipCriterion::MinMaxAngle;
+        streaming.triangulator.options.max_flips_per_triangle = 0;
+        assert_eq!(
+            streaming.push(Point::new(1.0, 2.9)),
+            Err(TriangulatorError::FlipLimitExceeded)
+        );
+
+        assert_eq!(streaming.points().len(), 4);
+        assert_eq!(streaming.triangles(), triangles.as_slice());
+        assert!(streaming
+            .triangulator
+            .validate(streaming.points())
+            .is_empty());
+    }
+}
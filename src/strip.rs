@@ -0,0 +1,141 @@
+use crate::{
+    delaunay_inc::tri_edge_mapping::TriangleEdgeMapping,
+    types::{PointIdx, TriIdx},
+    Edge, Triangle,
+};
+
+/// groups adjacent triangles into triangle strips, as used for rendering.
+/// strip[k], strip[k + 1], strip[k + 2] is a triangle, with the winding flipped for odd k, as gpus expect.
+/// greedy: each strip starts at the first unused triangle and grows across the edge of its last two vertices
+pub fn to_triangle_strips(triangles: &[Triangle]) -> Vec<Vec<PointIdx>> {
+    let mut mapping = TriangleEdgeMapping::with_capacity(triangles.len());
+    for idx in 0..triangles.len() {
+        mapping.add_triangle(idx as TriIdx, triangles);
+    }
+
+    let mut used = vec![false; triangles.len()];
+    let mut strips = Vec::new();
+
+    for start in 0..triangles.len() {
+        if used[start] {
+            continue;
+        }
+
+        // try starting along each edge of the triangle, keep the longest
+        let tri = &triangles[start];
+        let (strip, strip_triangles) = [
+            [tri.index0, tri.index1, tri.index2],
+            [tri.index1, tri.index2, tri.index0],
+            [tri.index2, tri.index0, tri.index1],
+        ]
+        .iter()
+        .map(|corners| grow_strip(start as TriIdx, corners, triangles, &mapping, &used))
+        .max_by_key(|(strip, _)| strip.len())
+        .unwrap();
+
+        for tri in strip_triangles {
+            used[tri as usize] = true;
+        }
+        strips.push(strip);
+    }
+
+    strips
+}
+
+fn grow_strip(
+    start: TriIdx,
+    corners: &[PointIdx; 3],
+    triangles: &[Triangle],
+    mapping: &TriangleEdgeMapping,
+    used: &[bool],
+) -> (Vec<PointIdx>, Vec<TriIdx>) {
+    let mut strip = corners.to_vec();
+    let mut strip_triangles = vec![start];
+
+    loop {
+        let (a, b) = (strip[strip.len() - 2], strip[strip.len() - 1]);
+        let next = mapping
+            .get_triangles(&Edge::new(a, b))
+            .into_iter()
+            .find(|tri| !used[*tri as usize] && !strip_triangles.contains(tri));
+        let next = match next {
+            Some(next) => next,
+            None => break,
+        };
+
+        let tri = &triangles[next as usize];
+        let third = [tri.index0, tri.index1, tri.index2]
+            .iter()
+            .copied()
+            .find(|idx| *idx != a && *idx != b)
+            .unwrap();
+        strip.push(third);
+        strip_triangles.push(next);
+    }
+
+    (strip, strip_triangles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{triangulate, Point};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn sorted(a: PointIdx, b: PointIdx, c: PointIdx) -> [PointIdx; 3] {
+        let mut corners = [a, b, c];
+        corners.sort_unstable();
+        corners
+    }
+
+    #[test]
+    fn strips_cover_every_triangle_once() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let points = (0..60)
+            .map(|_| Point::new(rng.gen_range(0.0..10.0), rng.gen_range(0.0..10.0)))
+            .collect::<Vec<_>>();
+        let triangles = triangulate(&points).unwrap();
+
+        let strips = to_triangle_strips(&triangles);
+
+        let mut from_strips = strips
+            .iter()
+            .flat_map(|strip| strip.windows(3).map(|w| sorted(w[0], w[1], w[2])))
+            .collect::<Vec<_>>();
+        let mut expected = triangles
+            .iter()
+            .map(|t| sorted(t.index0, t.index1, t.index2))
+            .collect::<Vec<_>>();
+        from_strips.sort_unstable();
+        expected.sort_unstable();
+
+        assert_eq!(from_strips, expected);
+        assert!(strips.len() < triangles.len());
+    }
+
+    #[test]
+    fn strip_keeps_winding() {
+        // two triangles of a square, counter clockwise
+        let triangles = [Triangle::new(0, 1, 2), Triangle::new(0, 2, 3)];
+
+        let strips = to_triangle_strips(&triangles);
+
+        assert_eq!(strips.len(), 1);
+        let strip = &strips[0];
+        assert_eq!(strip.len(), 4);
+        // odd triangles are flipped when drawn, so both come out counter clockwise
+        let second = Triangle::new(strip[2], strip[1], strip[3]);
+        let area = |t: &Triangle| {
+            let points = [
+                Point::new(0.0, 0.0),
+                Point::new(1.0, 0.0),
+                Point::new(1.0, 1.0),
+                Point::new(0.0, 1.0),
+            ];
+            let [a, b, c] = [t.index0, t.index1, t.index2].map(|i| points[i as usize]);
+            (b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y)
+        };
+        assert!(area(&Triangle::new(strip[0], strip[1], strip[2])) > 0.0);
+        assert!(area(&second) > 0.0);
+    }
+}
@@ -0,0 +1,193 @@
+use std::io::{self, Write};
+
+use crate::{edge::Edge, types::HashMap, Circle, Point, Triangle};
+
+/// writes the voronoi diagram, the dual of the delaunay triangulation, as svg lines clipped to bbox (min, max).
+/// interior delaunay edges give a line between the two circumcenters, boundary edges give an unbounded
+/// ray that is cut off at the bbox, which is also drawn to close the outer cells.
+/// y points down, as usual in svg
+pub fn write_voronoi_svg<W: Write>(
+    w: &mut W,
+    points: &[Point],
+    triangles: &[Triangle],
+    bbox: (Point, Point),
+) -> io::Result<()> {
+    let (min, max) = bbox;
+    writeln!(
+        w,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}">"#,
+        min.x,
+        min.y,
+        max.x - min.x,
+        max.y - min.y
+    )?;
+    writeln!(
+        w,
+        r#"<rect x="{}" y="{}" width="{}" height="{}" fill="none" stroke="black"/>"#,
+        min.x,
+        min.y,
+        max.x - min.x,
+        max.y - min.y
+    )?;
+
+    for (from, to) in voronoi_edges(points, triangles) {
+        let segment = match to {
+            VoronoiEnd::Point(to) => clip(&from, &(to - from), 1.0, &min, &max),
+            VoronoiEnd::Ray(dir) => clip(&from, &dir, f32::INFINITY, &min, &max),
+        };
+        if let Some((a, b)) = segment {
+            writeln!(
+                w,
+                r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="black"/>"#,
+                a.x, a.y, b.x, b.y
+            )?;
+        }
+    }
+
+    for p in points {
+        if p.x >= min.x && p.x <= max.x && p.y >= min.y && p.y <= max.y {
+            writeln!(w, r#"<circle cx="{}" cy="{}" r="1" fill="red"/>"#, p.x, p.y)?;
+        }
+    }
+
+    writeln!(w, "</svg>")
+}
+
+enum VoronoiEnd {
+    Point(Point),
+    Ray(Point),
+}
+
+/// one voronoi edge per delaunay edge, sorted by delaunay edge for stable output
+fn voronoi_edges(points: &[Point], triangles: &[Triangle]) -> Vec<(Point, VoronoiEnd)> {
+    let mut edge_tris = HashMap::<Edge, Vec<usize>>::default();
+    for (tri_idx, tri) in triangles.iter().enumerate() {
+        for edge in [
+            Edge::new(tri.index0, tri.index1),
+            Edge::new(tri.index1, tri.index2),
+            Edge::new(tri.index2, tri.index0),
+        ] {
+            edge_tris.entry(edge).or_default().push(tri_idx);
+        }
+    }
+    let mut edge_tris = edge_tris.into_iter().collect::<Vec<_>>();
+    edge_tris.sort_unstable_by_key(|(edge, _)| *edge);
+
+    let centers = triangles
+        .iter()
+        .map(|tri| Circle::from_triangle(tri, points).pos)
+        .collect::<Vec<_>>();
+    let finite = |p: &Point| p.x.is_finite() && p.y.is_finite();
+
+    edge_tris
+        .into_iter()
+        .filter_map(|(edge, tris)| match tris[..] {
+            [t0, t1] => Some((centers[t0], VoronoiEnd::Point(centers[t1]))),
+            [t0] => {
+                let tri = &triangles[t0];
+                let opposite = tri.index0 + tri.index1 + tri.index2 - edge.index_0 - edge.index_1;
+                let a = &points[edge.index_0 as usize];
+                let along = points[edge.index_1 as usize] - a;
+                let to_opposite = points[opposite as usize] - a;
+                let mut dir = Point::new(along.y, -along.x);
+                if dir.x * to_opposite.x + dir.y * to_opposite.y > 0.0 {
+                    dir = Point::new(-dir.x, -dir.y);
+                }
+                Some((centers[t0], VoronoiEnd::Ray(dir)))
+            }
+            _ => None,
+        })
+        .filter(|(from, to)| {
+            finite(from)
+                && match to {
+                    VoronoiEnd::Point(p) | VoronoiEnd::Ray(p) => finite(p),
+                }
+        })
+        .collect()
+}
+
+/// liang-barsky clipping of origin + t * dir, 0 <= t <= t_max, against the box
+fn clip(
+    origin: &Point,
+    dir: &Point,
+    t_max: f32,
+    min: &Point,
+    max: &Point,
+) -> Option<(Point, Point)> {
+    let mut t0 = 0.0f32;
+    let mut t1 = t_max;
+    for (p, q) in [
+        (-dir.x, origin.x - min.x),
+        (dir.x, max.x - origin.x),
+        (-dir.y, origin.y - min.y),
+        (dir.y, max.y - origin.y),
+    ] {
+        if p == 0.0 {
+            if q < 0.0 {
+                return None;
+            }
+        } else {
+            let t = q / p;
+            if p < 0.0 {
+                t0 = t0.max(t);
+            } else {
+                t1 = t1.min(t);
+            }
+        }
+    }
+
+    if t0 > t1 || !t1.is_finite() {
+        return None;
+    }
+    let at = |t: f32| Point::new(origin.x + t * dir.x, origin.y + t * dir.y);
+    Some((at(t0), at(t1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::triangulate;
+
+    fn render(points: &[Point], bbox: (Point, Point)) -> String {
+        let triangles = triangulate(points).unwrap();
+        let mut out = Vec::new();
+        write_voronoi_svg(&mut out, points, &triangles, bbox).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn one_line_per_delaunay_edge() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(2.0, 3.0),
+            Point::new(2.0, 1.0),
+        ];
+
+        let svg = render(&points, (Point::new(-10.0, -10.0), Point::new(10.0, 10.0)));
+
+        // 3 triangles, 3 interior edges between circumcenters and 3 rays out of the hull
+        assert_eq!(svg.matches("<line").count(), 6);
+        assert_eq!(svg.matches("<circle").count(), 4);
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+
+    #[test]
+    fn clips_to_bbox() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(2.0, 3.0),
+        ];
+
+        // the circumcenter is (2, 5/6), all three rays leave it
+        let svg = render(&points, (Point::new(1.0, 0.5), Point::new(3.0, 1.5)));
+        assert_eq!(svg.matches("<line").count(), 3);
+
+        let svg = render(
+            &points,
+            (Point::new(100.0, 100.0), Point::new(101.0, 101.0)),
+        );
+        assert_eq!(svg.matches("<line").count(), 0);
+    }
+}
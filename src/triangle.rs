@@ -1,6 +1,7 @@
-use crate::types::PointIdx;
+use crate::{edge::DirectedEdge, point::cross, types::PointIdx, Point};
 
 #[derive(PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Triangle {
     pub index0: PointIdx,
     pub index1: PointIdx,
@@ -16,6 +17,78 @@ impl Triangle {
         }
     }
 
+    /// index0 -> index1, index1 -> index2 and index2 -> index0, following the winding of the triangle
+    pub fn directed_edges(&self) -> [DirectedEdge; 3] {
+        [
+            DirectedEdge::new(self.index0, self.index1),
+            DirectedEdge::new(self.index1, self.index2),
+            DirectedEdge::new(self.index2, self.index0),
+        ]
+    }
+
+    pub fn area(&self, points: &[Point]) -> f32 {
+        0.5 * cross(
+            &points[self.index1 as usize],
+            &points[self.index2 as usize],
+            &points[self.index0 as usize],
+        )
+        .abs()
+    }
+
+    pub fn centroid(&self, points: &[Point]) -> Point {
+        let sum = points[self.index0 as usize]
+            + &points[self.index1 as usize]
+            + &points[self.index2 as usize];
+        sum * (1.0 / 3.0)
+    }
+
+    /// smallest interior angle, in radians
+    pub fn min_angle(&self, points: &[Point]) -> f32 {
+        self.angles(points)
+            .iter()
+            .copied()
+            .fold(f32::INFINITY, f32::min)
+    }
+
+    /// largest interior angle, in radians
+    pub fn max_angle(&self, points: &[Point]) -> f32 {
+        self.angles(points).iter().copied().fold(0.0, f32::max)
+    }
+
+    // interior angles at index0, index1 and index2
+    fn angles(&self, points: &[Point]) -> [f32; 3] {
+        let corners = [
+            self.index0 as usize,
+            self.index1 as usize,
+            self.index2 as usize,
+        ];
+        let angle = |i: usize| {
+            let corner = &points[corners[i]];
+            let a = points[corners[(i + 1) % 3]] - corner;
+            let b = points[corners[(i + 2) % 3]] - corner;
+            let dot = a.x * b.x + a.y * b.y;
+            let cross = a.x * b.y - a.y * b.x;
+            cross.abs().atan2(dot)
+        };
+        [angle(0), angle(1), angle(2)]
+    }
+
+    /// barycentric coordinates of point, weights for index0, index1 and index2. None for degenerate triangles
+    pub fn barycentric(&self, points: &[Point], point: &Point) -> Option<[f32; 3]> {
+        let a = &points[self.index0 as usize];
+        let b = &points[self.index1 as usize];
+        let c = &points[self.index2 as usize];
+
+        let area = cross(b, c, a);
+        if area.abs() < f32::EPSILON {
+            return None;
+        }
+
+        let w0 = cross(b, c, point) / area;
+        let w1 = cross(c, a, point) / area;
+        Some([w0, w1, 1.0 - w0 - w1])
+    }
+
     #[cfg(test)]
     pub(crate) fn equivalent(&self, other: &Triangle) -> bool {
         use std::collections::HashSet;
@@ -32,6 +105,24 @@ impl Triangle {
     }
 }
 
+/// (min, max) corners of the box around the points used by triangles, None if there are no triangles
+pub(crate) fn bounding_box(triangles: &[Triangle], points: &[Point]) -> Option<(Point, Point)> {
+    if triangles.is_empty() {
+        return None;
+    }
+
+    let mut min = Point::new(f32::INFINITY, f32::INFINITY);
+    let mut max = Point::new(f32::NEG_INFINITY, f32::NEG_INFINITY);
+    for tri in triangles {
+        for idx in [tri.index0, tri.index1, tri.index2] {
+            let p = &points[idx as usize];
+            min = Point::new(min.x.min(p.x), min.y.min(p.y));
+            max = Point::new(max.x.max(p.x), max.y.max(p.y));
+        }
+    }
+    Some((min, max))
+}
+
 // terser than derived Debug
 impl core::fmt::Debug for Triangle {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -0,0 +1,161 @@
+use std::collections::hash_map::Entry;
+
+use crate::{
+    types::{HashMap, PointIdx},
+    Edge, Neighbours, Point, Triangle, TriangulatorError,
+};
+
+/// triangles of the unique input points, with the input index of each of them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Triangulation {
+    /// indices into the unique points, ie index_map
+    pub triangles: Vec<Triangle>,
+    /// index_map[output_idx] is the index of the point in the input
+    pub index_map: Vec<PointIdx>,
+}
+
+/// a triangulation with its edges and adjacency, see triangulate_full
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FullTriangulation {
+    pub triangles: Vec<Triangle>,
+    /// all edges of the triangles, sorted
+    pub edges: Vec<Edge>,
+    /// the edges used by only one triangle, sorted
+    pub boundary: Vec<Edge>,
+    /// neighbours[t][e] is the triangle across edge e of triangles[t], as for triangulate_with_neighbours
+    pub neighbours: Vec<Neighbours>,
+}
+
+/// counts of a triangulation, see Triangulator::topology
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Topology {
+    /// points used by any triangle
+    pub num_vertices: usize,
+    pub num_edges: usize,
+    pub num_triangles: usize,
+    /// groups of triangles connected through shared edges
+    pub num_components: usize,
+    /// num_vertices - num_edges + num_triangles, not counting the outer face.
+    /// the number of components minus the number of holes, so 1 for a triangulated point set
+    pub euler_characteristic: i64,
+}
+
+/// triangulates after collapsing points with identical coordinates, keeping the first of them
+pub(crate) fn triangulate_dedup(points: &[Point]) -> Result<Triangulation, TriangulatorError> {
+    let (unique, index_map) = dedup(points);
+
+    let triangles = crate::triangulate(&unique).map_err(|err| match err {
+        TriangulatorError::NANInInput(i) => TriangulatorError::NANInInput(index_map[i] as usize),
+        err => err,
+    })?;
+
+    Ok(Triangulation {
+        triangles,
+        index_map,
+    })
+}
+
+/// triangulate_dedup, with the attribute of each kept point in the order the triangles index them
+pub(crate) fn triangulate_with<A: Clone>(
+    points: &[(Point, A)],
+) -> Result<(Vec<Triangle>, Vec<A>), TriangulatorError> {
+    let positions = points.iter().map(|(p, _)| *p).collect::<Vec<_>>();
+    let triangulation = triangulate_dedup(&positions)?;
+
+    let attributes = triangulation
+        .index_map
+        .iter()
+        .map(|idx| points[*idx as usize].1.clone())
+        .collect();
+    Ok((triangulation.triangles, attributes))
+}
+
+fn dedup(points: &[Point]) -> (Vec<Point>, Vec<PointIdx>) {
+    let mut seen = HashMap::default();
+    let mut unique = Vec::with_capacity(points.len());
+    let mut index_map = Vec::with_capacity(points.len());
+
+    for (i, p) in points.iter().enumerate() {
+        // adding 0.0 turns -0.0 into 0.0, so they are considered the same
+        let key = ((p.x + 0.0).to_bits(), (p.y + 0.0).to_bits());
+        if let Entry::Vacant(entry) = seen.entry(key) {
+            entry.insert(unique.len());
+            unique.push(*p);
+            index_map.push(i as PointIdx);
+        }
+    }
+
+    (unique, index_map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_map_round_trips() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.0, 0.0),
+            Point::new(0.5, 1.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.5, 0.4),
+            Point::new(-0.0, 0.0),
+        ];
+
+        let triangulation = triangulate_dedup(&points).unwrap();
+
+        assert_eq!(triangulation.index_map, vec![0, 1, 3, 5]);
+        assert_eq!(triangulation.triangles.len(), 3);
+
+        let unique = triangulation
+            .index_map
+            .iter()
+            .map(|i| points[*i as usize])
+            .collect::<Vec<_>>();
+        assert_eq!(
+            crate::triangulate(&unique).unwrap(),
+            triangulation.triangles
+        );
+        for (output_idx, input_idx) in triangulation.index_map.iter().enumerate() {
+            assert_eq!(unique[output_idx].x, points[*input_idx as usize].x);
+            assert_eq!(unique[output_idx].y, points[*input_idx as usize].y);
+        }
+    }
+
+    #[test]
+    fn attributes_follow_points_through_dedup() {
+        let points = [
+            (Point::new(0.0, 0.0), "a"),
+            (Point::new(1.0, 0.0), "b"),
+            (Point::new(0.0, 0.0), "duplicate of a"),
+            (Point::new(0.5, 1.0), "c"),
+            (Point::new(0.5, 0.4), "d"),
+        ];
+
+        let (triangles, attributes) = triangulate_with(&points).unwrap();
+
+        assert_eq!(attributes, ["a", "b", "c", "d"]);
+        let unique = [0, 1, 3, 4]
+            .iter()
+            .map(|i| points[*i].0)
+            .collect::<Vec<_>>();
+        assert_eq!(triangles, crate::triangulate(&unique).unwrap());
+    }
+
+    #[test]
+    fn reports_nan_at_input_index() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.5, f32::NAN),
+        ];
+
+        assert_eq!(
+            triangulate_dedup(&points),
+            Err(TriangulatorError::NANInInput(3))
+        );
+    }
+}
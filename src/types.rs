@@ -1,2 +1,39 @@
+use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault};
+
+/// index types, u32 with the small-indices feature to halve the memory of triangles and edge maps
+#[cfg(not(feature = "small-indices"))]
 pub type TriIdx = usize;
+#[cfg(not(feature = "small-indices"))]
 pub type PointIdx = usize;
+#[cfg(feature = "small-indices")]
+pub type TriIdx = u32;
+#[cfg(feature = "small-indices")]
+pub type PointIdx = u32;
+
+/// the triangles across edge 0, 1 and 2 of a triangle, None on the hull
+pub type Neighbours = [Option<TriIdx>; 3];
+
+// hash containers with a fixed hasher, so iteration order, and thus the triangulation, is the same between runs
+pub(crate) type FixedState = BuildHasherDefault<DefaultHasher>;
+pub(crate) type HashMap<K, V> = std::collections::HashMap<K, V, FixedState>;
+pub(crate) type HashSet<K> = std::collections::HashSet<K, FixedState>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Triangle;
+    use std::mem::size_of;
+
+    #[test]
+    fn index_width_follows_feature() {
+        let width = if cfg!(feature = "small-indices") {
+            4
+        } else {
+            size_of::<usize>()
+        };
+
+        assert_eq!(size_of::<PointIdx>(), width);
+        assert_eq!(size_of::<TriIdx>(), width);
+        assert_eq!(size_of::<Triangle>(), 3 * width);
+    }
+}
@@ -1,3 +1,4 @@
+#[allow(dead_code)]
 pub(crate) struct SliceDisplay<'a, T: 'a>(pub &'a [T]);
 
 impl<'a, T: std::fmt::Display + 'a> std::fmt::Display for SliceDisplay<'a, T> {
@@ -19,6 +20,36 @@ impl<'a, T: std::fmt::Display + 'a> std::fmt::Display for SliceDisplay<'a, T> {
     }
 }
 
+/// (de)serializes a map as a list of key-value pairs, for maps whose keys aren't strings
+#[cfg(feature = "serde")]
+pub(crate) mod map_as_list {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::{
+        collections::HashMap,
+        hash::{BuildHasher, Hash},
+    };
+
+    pub fn serialize<K, V, H, S>(map: &HashMap<K, V, H>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        K: Serialize,
+        V: Serialize,
+        S: Serializer,
+    {
+        serializer.collect_seq(map.iter())
+    }
+
+    pub fn deserialize<'de, K, V, H, D>(deserializer: D) -> Result<HashMap<K, V, H>, D::Error>
+    where
+        K: Deserialize<'de> + Eq + Hash,
+        V: Deserialize<'de>,
+        H: BuildHasher + Default,
+        D: Deserializer<'de>,
+    {
+        let pairs = Vec::<(K, V)>::deserialize(deserializer)?;
+        Ok(pairs.into_iter().collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
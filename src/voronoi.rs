@@ -0,0 +1,50 @@
+use std::cmp::Ordering;
+
+use crate::{circle::circumcenter, Point, Triangle};
+
+/// the corners of the voronoi cell of each point, ie the circumcenters of the triangles around it, in no particular order.
+/// the cells of hull points are unbounded, their corners are only the finite part
+pub(crate) fn cell_corners(points: &[Point], triangles: &[Triangle]) -> Vec<Vec<Point>> {
+    let mut cells = vec![Vec::new(); points.len()];
+    for tri in triangles.iter() {
+        let center = circumcenter(
+            &points[tri.index0 as usize],
+            &points[tri.index1 as usize],
+            &points[tri.index2 as usize],
+        );
+        for idx in [tri.index0, tri.index1, tri.index2] {
+            cells[idx as usize].push(center);
+        }
+    }
+    cells
+}
+
+// orders the corners of a convex cell counter clockwise around site
+fn sort_around(site: &Point, corners: &mut [Point]) {
+    let angle = |p: &Point| (p.y - site.y).atan2(p.x - site.x);
+    corners.sort_by(|a, b| angle(a).partial_cmp(&angle(b)).unwrap_or(Ordering::Equal));
+}
+
+/// twice the signed area of the convex cell around site, with corners in any order, and the sums giving its
+/// centroid relative to site when divided by three times that area. sorts the corners counter clockwise
+pub(crate) fn cell_moments(site: &Point, corners: &mut [Point]) -> (f32, Point) {
+    sort_around(site, corners);
+
+    // shoelace, relative to site to keep the products small
+    let mut area = 0.0;
+    let mut sums = Point::new(0.0, 0.0);
+    for i in 0..corners.len() {
+        let a = corners[i] - site;
+        let b = corners[(i + 1) % corners.len()] - site;
+        let cross = a.x * b.y - b.x * a.y;
+        area += cross;
+        sums.x += (a.x + b.x) * cross;
+        sums.y += (a.y + b.y) * cross;
+    }
+    (area, sums)
+}
+
+/// area of the convex cell around site, with corners in any order
+pub(crate) fn cell_area(site: &Point, corners: &mut [Point]) -> f32 {
+    0.5 * cell_moments(site, corners).0
+}
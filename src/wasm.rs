@@ -0,0 +1,33 @@
+use wasm_bindgen::prelude::*;
+
+/// triangulates interleaved x,y pairs and returns a flat index buffer, three indices per triangle.
+/// errors are thrown as JS exceptions.
+#[wasm_bindgen]
+pub fn triangulate_flat(coords: &[f32]) -> Result<Vec<u32>, JsValue> {
+    let triangles =
+        crate::triangulate_flat(coords).map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    let mut indices = Vec::with_capacity(triangles.len() * 3);
+    for tri in triangles.iter() {
+        indices.push(tri.index0 as u32);
+        indices.push(tri.index1 as u32);
+        indices.push(tri.index2 as u32);
+    }
+    Ok(indices)
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn triangulates_square() {
+        let coords = [0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0];
+
+        let indices = triangulate_flat(&coords).unwrap();
+
+        assert_eq!(indices.len(), 6);
+        assert!(indices.iter().all(|i| *i < 4));
+    }
+}